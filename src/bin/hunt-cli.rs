@@ -0,0 +1,345 @@
+//! Generates loadouts from the command line, the same way the website does, for scripts and bots.
+
+#![warn(
+    clippy::all,
+    clippy::pedantic,
+    clippy::nursery,
+    clippy::cargo,
+    clippy::style
+)]
+
+use serde::Serialize;
+use std::{env, process, str::FromStr};
+
+use hunt_app::content::{
+    generic_item::GenericItemLockable, ConsumableSlotPreference, GenericItem, ToolSlotPreference,
+    WeaponSlotPreference,
+};
+use hunt_app::randomizer::{
+    balance,
+    config::ToggleOption,
+    loadout,
+    loadout_text::{self, TextFormat},
+    theme::{Theme, ThemeFallback},
+    Budget, Config, Loadout,
+};
+
+const USAGE: &str = "Usage: hunt-cli [OPTIONS]
+
+Options:
+    --max-cost <COST>             Most the loadout can cost
+    --min-cost <COST>             Least the loadout should cost
+    --max-rank <RANK>             Highest bloodline rank items can require
+    --enable <OPTION>             Turn an option on, can be repeated
+    --disable <OPTION>            Turn an option off, can be repeated
+    --tool-preferences <PREFS>    Up to 4 comma separated tool slot preferences
+    --weapon-preferences <PREFS>  Up to 2 comma separated weapon slot preferences
+    --consumable-preferences <PREFS>
+                                  Up to 4 comma separated consumable slot preferences
+    --theme <THEME>               Favour items fitting a theme
+    --theme-fallback <FALLBACK>   What a slot gets when nothing fits the theme [default: AnyItem]
+    --seed <SEED>                 Seed for the first loadout, the rest use the following seeds
+    --count <COUNT>               How many loadouts to generate [default: 1]
+    --balance <TOLERANCE>         Reroll until every loadout costs within this much of each other
+    --format <FORMAT>             text, markdown or json [default: text]
+    -h, --help                    Print this message
+
+Options are DualWield, DuplicateWeapons, CustomAmmo, Quartermaster, AlwaysDualWield,
+AlwaysDuplicateWeapons, AlwaysCustomAmmo, AlwaysQuartermaster and BestValue. DualWield,
+DuplicateWeapons and CustomAmmo are on by default.
+
+Tool slot preferences are NoPreference, Medkit, Melee, Throwable, Tripmines, Decoys and Others.
+
+Weapon slot preferences are NoPreference, Shotgun, LongRifle, Pistol, Bow, Melee and Explosive.
+
+Consumable slot preferences are NoPreference, Healing, Antidote, Stamina, Explosives, Fire, Poison
+and Choke.
+
+Themes are NoTheme, Silent, Fire, Poison and Explosives. Theme fallbacks are AnyItem and Empty.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+impl TryFrom<String> for OutputFormat {
+    type Error = &'static str;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match &*val {
+            "text" => Ok(Self::Text),
+            "markdown" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            _ => Err("Invalid format, expected text, markdown or json"),
+        }
+    }
+}
+
+struct Args {
+    config: Config,
+    count: u32,
+    balance: Option<u16>,
+    format: OutputFormat,
+    help: bool,
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next().ok_or_else(|| format!("{flag} needs a value"))
+}
+
+fn parse_number<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{flag} expects a number, got {value}"))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        config: Config::default(),
+        count: 1,
+        balance: None,
+        format: OutputFormat::Text,
+        help: false,
+    };
+
+    while let Some(flag) = args.next() {
+        // Only a flag asks for help, in `--format -h` it is the value.
+        if flag == "-h" || flag == "--help" {
+            parsed.help = true;
+            break;
+        }
+
+        let value = next_value(&mut args, &flag)?;
+
+        match flag.as_str() {
+            "--max-cost" => parsed.config.max_cost = Some(parse_number(&flag, &value)?),
+            "--min-cost" => parsed.config.min_cost = Some(parse_number(&flag, &value)?),
+            "--max-rank" => parsed.config.max_rank = parse_number(&flag, &value)?,
+            "--seed" => parsed.config.seed = Some(parse_number(&flag, &value)?),
+            "--count" => parsed.count = parse_number(&flag, &value)?,
+            "--balance" => parsed.balance = Some(parse_number(&flag, &value)?),
+            "--enable" | "--disable" => {
+                let option = ToggleOption::try_from(value.clone())
+                    .map_err(|err| format!("{err}: {value}"))?;
+
+                parsed.config.remove_option(option);
+
+                if flag == "--enable" {
+                    parsed.config.toggled_options.push(option);
+                }
+            }
+            "--tool-preferences" => {
+                let preferences = value.split(',').collect::<Vec<&str>>();
+
+                if preferences.len() > parsed.config.tool_preferences.len() {
+                    return Err("There are only 4 tool slots".to_string());
+                }
+
+                for (slot, preference) in preferences.into_iter().enumerate() {
+                    parsed.config.tool_preferences[slot] =
+                        ToolSlotPreference::try_from(preference.trim().to_string())
+                            .map_err(|err| format!("{err}: {preference}"))?;
+                }
+            }
+            "--weapon-preferences" => {
+                let preferences = value.split(',').collect::<Vec<&str>>();
+
+                if preferences.len() > parsed.config.weapon_preferences.len() {
+                    return Err("There are only 2 weapon slots".to_string());
+                }
+
+                for (slot, preference) in preferences.into_iter().enumerate() {
+                    parsed.config.weapon_preferences[slot] =
+                        WeaponSlotPreference::try_from(preference.trim().to_string())
+                            .map_err(|err| format!("{err}: {preference}"))?;
+                }
+            }
+            "--consumable-preferences" => {
+                let preferences = value.split(',').collect::<Vec<&str>>();
+
+                if preferences.len() > parsed.config.consumable_preferences.len() {
+                    return Err("There are only 4 consumable slots".to_string());
+                }
+
+                for (slot, preference) in preferences.into_iter().enumerate() {
+                    parsed.config.consumable_preferences[slot] =
+                        ConsumableSlotPreference::try_from(preference.trim().to_string())
+                            .map_err(|err| format!("{err}: {preference}"))?;
+                }
+            }
+            "--theme" => {
+                parsed.config.theme =
+                    Theme::try_from(value.clone()).map_err(|err| format!("{err}: {value}"))?;
+            }
+            "--theme-fallback" => {
+                parsed.config.theme_fallback = ThemeFallback::try_from(value.clone())
+                    .map_err(|err| format!("{err}: {value}"))?;
+            }
+            "--format" => parsed.format = OutputFormat::try_from(value)?,
+            _ => return Err(format!("Unknown option {flag}")),
+        }
+    }
+
+    Ok(parsed)
+}
+
+#[derive(Serialize)]
+struct WeaponOutput {
+    name: String,
+    dual_wield: bool,
+    ammo: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct LoadoutOutput {
+    seed: Option<u64>,
+    total_cost: u16,
+    weapons: Vec<WeaponOutput>,
+    tools: Vec<String>,
+    consumables: Vec<String>,
+    errors: Vec<String>,
+}
+
+fn item_names(items: &[GenericItemLockable]) -> Vec<String> {
+    items
+        .iter()
+        .filter_map(|item| item.item.as_ref().map(GenericItem::to_full_name))
+        .collect()
+}
+
+impl LoadoutOutput {
+    fn new(loadout: &Loadout, budget: &Budget) -> Self {
+        let weapons = [&loadout.weapon_one, &loadout.weapon_two]
+            .into_iter()
+            .filter_map(|weapon| weapon.item.as_ref())
+            .map(|weapon| WeaponOutput {
+                name: weapon.to_full_name(),
+                dual_wield: weapon.dual_wield,
+                ammo: weapon
+                    .ammo_equipped
+                    .iter()
+                    .map(loadout_text::ammo_name)
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            seed: loadout.seed,
+            total_cost: budget.total_cost,
+            weapons,
+            tools: item_names(&loadout.tools),
+            consumables: item_names(&loadout.consumables),
+            errors: loadout.errors.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+fn print_text(loadout: &Loadout, budget: &Budget, format: TextFormat) {
+    if let Some(seed) = loadout.seed {
+        println!("Seed: {seed}");
+    }
+
+    println!("{}", loadout_text::format_loadout(loadout, budget, format));
+
+    if !loadout.errors.is_empty() {
+        println!("Errors:");
+        for error in &loadout.errors {
+            println!("  {error}");
+        }
+    }
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    if args.help {
+        println!("{USAGE}");
+        return;
+    }
+
+    let (loadouts, spread) = args.balance.map_or_else(
+        || (generate(&args), None),
+        |tolerance| {
+            let (loadouts, spread) = generate_balanced(&args, tolerance);
+            (loadouts, Some(spread))
+        },
+    );
+
+    let text_format = match args.format {
+        OutputFormat::Text => TextFormat::Plain,
+        OutputFormat::Markdown => TextFormat::Markdown,
+        OutputFormat::Json => {
+            let outputs = loadouts
+                .iter()
+                .map(|(loadout, budget)| LoadoutOutput::new(loadout, budget))
+                .collect::<Vec<LoadoutOutput>>();
+
+            print_json(&outputs);
+
+            // Keeps stdout a plain list of loadouts.
+            if let Some(spread) = spread {
+                eprintln!("Cost Spread: {spread}");
+            }
+            return;
+        }
+    };
+
+    for (index, (loadout, budget)) in loadouts.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+
+        print_text(loadout, budget, text_format);
+    }
+
+    if let Some(spread) = spread {
+        println!("\nCost Spread: {spread}");
+    }
+}
+
+fn generate(args: &Args) -> Vec<(Loadout, Budget)> {
+    (0..args.count)
+        .map(|index| {
+            // Offsetting the seed keeps a batch repeatable without every loadout being the same.
+            let mut config = args.config.clone();
+            config.seed = args
+                .config
+                .seed
+                .map(|seed| seed.wrapping_add(u64::from(index)));
+
+            let mut loadout = Loadout::default();
+            let mut budget = Budget::default();
+            loadout::random(&mut loadout, &mut budget, &config);
+
+            (loadout, budget)
+        })
+        .collect()
+}
+
+fn generate_balanced(args: &Args, tolerance: u16) -> (Vec<(Loadout, Budget)>, u16) {
+    let count = usize::try_from(args.count).unwrap_or_default();
+    let mut loadouts = vec![Loadout::default(); count];
+    let mut budgets = vec![Budget::default(); count];
+
+    let spread = balance::random_balanced(&mut loadouts, &mut budgets, &args.config, tolerance);
+
+    (loadouts.into_iter().zip(budgets).collect(), spread)
+}
+
+fn print_json(outputs: &[LoadoutOutput]) {
+    match serde_json::to_string_pretty(outputs) {
+        Ok(json) => println!("{json}"),
+        Err(err) => {
+            eprintln!("Unable to serialize loadouts: {err}");
+            process::exit(1);
+        }
+    }
+}
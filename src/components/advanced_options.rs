@@ -2,8 +2,18 @@ use wasm_bindgen::JsCast;
 use web_sys::{EventTarget, HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
-use crate::content::ToolSlotPreference;
-use crate::randomizer::{config::ToggleOption, Config};
+use crate::components::{ExclusionList, ItemLimits, PresetManager, RequiredItems};
+use crate::content::{
+    BulletVariant, ConsumableSlotPreference, ToolSlotPreference, WeaponSlotPreference,
+};
+use crate::randomizer::{
+    budget::{BudgetSplit, LeftoverPolicy},
+    config::ToggleOption,
+    limits::ItemLimit,
+    theme::{Theme, ThemeFallback},
+    unlocks::UnlockKey,
+    Config,
+};
 
 #[derive(PartialEq, Properties)]
 pub struct AdvancedOptionsProps {
@@ -25,13 +35,30 @@ pub fn AdvancedOptions(props: &AdvancedOptionsProps) -> Html {
 
     let on_options_close_click = {
         let on_options_close = on_options_close.clone();
-        let config = config.clone();
+        let mut config = config.clone();
+
+        // An invalid split can't be used to budget, keep the last valid one instead.
+        if config
+            .budget_split
+            .as_ref()
+            .is_some_and(|split| !split.is_valid())
+        {
+            config.budget_split.clone_from(&props.config.budget_split);
+        }
 
         move |_| {
             on_options_close.emit(config.clone());
         }
     };
 
+    let on_preset_apply = {
+        let config_handle = config_handle.clone();
+
+        Callback::from(move |config: Config| {
+            config_handle.set(config);
+        })
+    };
+
     let on_bloodline_rank_input = {
         let config_handle = config_handle.clone();
         let config = config.clone();
@@ -75,6 +102,207 @@ pub fn AdvancedOptions(props: &AdvancedOptionsProps) -> Html {
         }
     };
 
+    let on_min_cost_input = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        move |e: InputEvent| {
+            let target: Option<EventTarget> = e.target();
+
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+            if let Some(input) = input {
+                let mut config = config.clone();
+
+                if input.value().is_empty() {
+                    config.min_cost = None;
+                } else {
+                    config.min_cost = input.value().parse::<u16>().ok();
+                }
+
+                config_handle.set(config);
+            }
+        }
+    };
+
+    let on_ammo_chance_input = |set_chance: fn(&mut Config, f32)| {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        move |e: InputEvent| {
+            let target: Option<EventTarget> = e.target();
+
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+            if let Some(input) = input {
+                let mut config = config.clone();
+
+                if let Ok(chance) = input.value().parse::<f32>() {
+                    set_chance(&mut config, chance);
+                    config_handle.set(config);
+                }
+            }
+        }
+    };
+
+    let on_budget_split_input = |set_percent: fn(&mut Config, u8)| {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        move |e: InputEvent| {
+            let target: Option<EventTarget> = e.target();
+
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+            if let Some(input) = input {
+                let mut config = config.clone();
+
+                if let Ok(percent) = input.value().parse::<u8>() {
+                    set_percent(&mut config, percent);
+                    config_handle.set(config);
+                }
+            }
+        }
+    };
+
+    let on_custom_split_toggle = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        move |_| {
+            let mut config = config.clone();
+
+            config.budget_split = if config.budget_split.is_some() {
+                None
+            } else {
+                Some(BudgetSplit::default())
+            };
+
+            config_handle.set(config);
+        }
+    };
+
+    let on_leftover_policy_changed = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        move |e: Event| {
+            let target: Option<EventTarget> = e.target();
+
+            let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+
+            if let Some(select) = select {
+                let mut config = config.clone();
+                let value = select.value();
+
+                config.leftover_policy = value
+                    .clone()
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("Cannot conversion {value} to leftover policy."));
+                config_handle.set(config);
+            }
+        }
+    };
+
+    let on_theme_changed = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        move |e: Event| {
+            let target: Option<EventTarget> = e.target();
+
+            let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+
+            if let Some(select) = select {
+                let mut config = config.clone();
+                let value = select.value();
+
+                config.theme = value
+                    .clone()
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("Cannot conversion {value} to theme."));
+                config_handle.set(config);
+            }
+        }
+    };
+
+    let on_theme_fallback_changed = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        move |e: Event| {
+            let target: Option<EventTarget> = e.target();
+
+            let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+
+            if let Some(select) = select {
+                let mut config = config.clone();
+                let value = select.value();
+
+                config.theme_fallback = value
+                    .clone()
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("Cannot conversion {value} to theme fallback."));
+                config_handle.set(config);
+            }
+        }
+    };
+
+    let on_excluded_item_toggle = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        Callback::from(move |key: UnlockKey| {
+            let mut config = config.clone();
+            config.toggle_excluded_item(key);
+            config_handle.set(config);
+        })
+    };
+
+    let on_excluded_ammo_toggle = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        Callback::from(move |ammo: BulletVariant| {
+            let mut config = config.clone();
+            config.toggle_excluded_ammo(ammo);
+            config_handle.set(config);
+        })
+    };
+
+    let on_required_items_change = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        Callback::from(move |required_items: Vec<UnlockKey>| {
+            let mut config = config.clone();
+            config.required_items = required_items;
+            config_handle.set(config);
+        })
+    };
+
+    let on_required_groups_change = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        Callback::from(move |required_groups: Vec<Vec<UnlockKey>>| {
+            let mut config = config.clone();
+            config.required_groups = required_groups;
+            config_handle.set(config);
+        })
+    };
+
+    let on_item_limits_change = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        Callback::from(move |item_limits: Vec<ItemLimit>| {
+            let mut config = config.clone();
+            config.item_limits = item_limits;
+            config_handle.set(config);
+        })
+    };
+
     let on_dualwield_toggle = {
         let config_handle = config_handle.clone();
         let config = config.clone();
@@ -143,6 +371,28 @@ pub fn AdvancedOptions(props: &AdvancedOptionsProps) -> Html {
         }
     };
 
+    let on_only_unlocked_toggle = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        move |_| {
+            let mut config = config.clone();
+            config.toggle_option(ToggleOption::OnlyUnlocked);
+            config_handle.set(config);
+        }
+    };
+
+    let on_best_value_toggle = {
+        let config_handle = config_handle.clone();
+        let config = config.clone();
+
+        move |_| {
+            let mut config = config.clone();
+            config.toggle_option(ToggleOption::BestValue);
+            config_handle.set(config);
+        }
+    };
+
     let on_always_dual_wield_toggle = {
         let config_handle = config_handle.clone();
         let config = config.clone();
@@ -251,8 +501,84 @@ pub fn AdvancedOptions(props: &AdvancedOptionsProps) -> Html {
         None
     };
 
+    // Pooled budgets ignore the split so there's nothing to validate.
+    let split_invalid = config.leftover_policy != LeftoverPolicy::Pool
+        && config
+            .budget_split
+            .as_ref()
+            .is_some_and(|split| !split.is_valid());
+    let split_disabled =
+        config.leftover_policy == LeftoverPolicy::Pool || config.budget_split.is_none();
+    let budget_split = config.budget_split.clone().unwrap_or_default();
+
     let is_active = if *is_active { Some("is-active") } else { None };
 
+    let weapon_preferences_html = config
+        .weapon_preferences
+        .iter()
+        .enumerate()
+        .map(|(slot, slot_preference)| {
+            let config_handle = config_handle.clone();
+            let config = config.clone();
+
+            let on_select_changed = {
+                move |e: Event| {
+                    let target: Option<EventTarget> = e.target();
+
+                    let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+
+                    if let Some(select) = select {
+                        let mut config = config.clone();
+                        let value = select.value();
+
+                        config.weapon_preferences[slot] =
+                            value.clone().try_into().unwrap_or_else(|_| {
+                                panic!("Cannot conversion {value} to weapon_preferences slot.")
+                            });
+                        config_handle.set(config);
+                    }
+                }
+            };
+
+            html! {
+                <div class={classes!("column")}>
+                    <div class={classes!("select")}>
+                        <select onchange={on_select_changed}>
+                            <option
+                                selected={*slot_preference == WeaponSlotPreference::NoPreference}
+                                value={WeaponSlotPreference::NoPreference.to_string()}
+                            >{"No Preference"}</option>
+                            <option
+                                selected={*slot_preference == WeaponSlotPreference::Shotgun}
+                                value={WeaponSlotPreference::Shotgun.to_string()}
+                            >{"Shotgun"}</option>
+                            <option
+                                selected={*slot_preference == WeaponSlotPreference::LongRifle}
+                                value={WeaponSlotPreference::LongRifle.to_string()}
+                            >{"Sniper / Long Rifle"}</option>
+                            <option
+                                selected={*slot_preference == WeaponSlotPreference::Pistol}
+                                value={WeaponSlotPreference::Pistol.to_string()}
+                            >{"Pistol"}</option>
+                            <option
+                                selected={*slot_preference == WeaponSlotPreference::Bow}
+                                value={WeaponSlotPreference::Bow.to_string()}
+                            >{"Bow / Crossbow"}</option>
+                            <option
+                                selected={*slot_preference == WeaponSlotPreference::Melee}
+                                value={WeaponSlotPreference::Melee.to_string()}
+                            >{"Melee Only"}</option>
+                            <option
+                                selected={*slot_preference == WeaponSlotPreference::Explosive}
+                                value={WeaponSlotPreference::Explosive.to_string()}
+                            >{"Explosive"}</option>
+                        </select>
+                    </div>
+                </div>
+            }
+        })
+        .collect::<Html>();
+
     let tool_preferences_html = config
         .tool_preferences
         .iter()
@@ -319,17 +645,91 @@ pub fn AdvancedOptions(props: &AdvancedOptionsProps) -> Html {
         })
         .collect::<Html>();
 
+    let consumable_preferences_html = config
+        .consumable_preferences
+        .iter()
+        .enumerate()
+        .map(|(slot, slot_preference)| {
+            let config_handle = config_handle.clone();
+            let config = config.clone();
+
+            let on_select_changed = {
+                move |e: Event| {
+                    let target: Option<EventTarget> = e.target();
+
+                    let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+
+                    if let Some(select) = select {
+                        let mut config = config.clone();
+                        let value = select.value();
+
+                        config.consumable_preferences[slot] =
+                            value.clone().try_into().unwrap_or_else(|_| {
+                                panic!("Cannot conversion {value} to consumable_preferences slot.")
+                            });
+                        config_handle.set(config);
+                    }
+                }
+            };
+
+            html! {
+                <div class={classes!("column")}>
+                    <div class={classes!("select")}>
+                        <select onchange={on_select_changed}>
+                            <option
+                                selected={*slot_preference == ConsumableSlotPreference::NoPreference}
+                                value={ConsumableSlotPreference::NoPreference.to_string()}
+                            >{"No Preference"}</option>
+                            <option
+                                selected={*slot_preference == ConsumableSlotPreference::Healing}
+                                value={ConsumableSlotPreference::Healing.to_string()}
+                            >{"Healing"}</option>
+                            <option
+                                selected={*slot_preference == ConsumableSlotPreference::Antidote}
+                                value={ConsumableSlotPreference::Antidote.to_string()}
+                            >{"Antidote"}</option>
+                            <option
+                                selected={*slot_preference == ConsumableSlotPreference::Stamina}
+                                value={ConsumableSlotPreference::Stamina.to_string()}
+                            >{"Stamina"}</option>
+                            <option
+                                selected={*slot_preference == ConsumableSlotPreference::Explosives}
+                                value={ConsumableSlotPreference::Explosives.to_string()}
+                            >{"Explosives"}</option>
+                            <option
+                                selected={*slot_preference == ConsumableSlotPreference::Fire}
+                                value={ConsumableSlotPreference::Fire.to_string()}
+                            >{"Fire"}</option>
+                            <option
+                                selected={*slot_preference == ConsumableSlotPreference::Poison}
+                                value={ConsumableSlotPreference::Poison.to_string()}
+                            >{"Poison"}</option>
+                            <option
+                                selected={*slot_preference == ConsumableSlotPreference::Choke}
+                                value={ConsumableSlotPreference::Choke.to_string()}
+                            >{"Choke"}</option>
+                        </select>
+                    </div>
+                </div>
+            }
+        })
+        .collect::<Html>();
+
     html! {
         <div class={classes!("modal", is_active)}>
             <div class={classes!("modal-background")} onclick={on_options_close_click.clone()}></div>
 
             <div class={classes!("modal-content", "options-container")}>
+                <p class={classes!("subtitle", "has-text-centered")}>{"Presets"}</p>
+
+                <PresetManager config={config.clone()} on_preset_apply={on_preset_apply} />
+
                 <p class={classes!("subtitle", "has-text-centered")}>{"General Limits"}</p>
 
                 <div class={classes!("columns", "is-centered")}>
                     <div class={classes!("column")}>
                         <div class={classes!("field")}>
-                            <label class={classes!("label")}>{"Bloodline Rank (Ignored for now)"}</label>
+                            <label class={classes!("label")}>{"Bloodline Rank"}</label>
                             <div class={classes!("control")}>
                                 <input
                                     class={classes!("input")}
@@ -343,6 +743,21 @@ pub fn AdvancedOptions(props: &AdvancedOptionsProps) -> Html {
                     </div>
 
 
+                    <div class={classes!("column")}>
+                        <div class={classes!("field")}>
+                            <label class={classes!("label")}>{"Min Cost"}</label>
+                            <div class={classes!("control")}>
+                                <input
+                                    class={classes!("input")}
+                                    type="number"
+                                    placeholder={"Min Cost"}
+                                    value={config.min_cost.map_or_else(String::new, |cost| cost.to_string())}
+                                    oninput={on_min_cost_input}
+                                />
+                            </div>
+                        </div>
+                    </div>
+
                     <div class={classes!("column")}>
                         <div class={classes!("field")}>
                             <label class={classes!("label")}>{"Max Cost"}</label>
@@ -359,6 +774,162 @@ pub fn AdvancedOptions(props: &AdvancedOptionsProps) -> Html {
                     </div>
                 </div>
 
+                <p class={classes!("subtitle", "has-text-centered")}>{"Budget Split"}</p>
+
+                <div class={classes!("has-text-centered", "mb-3")}>
+                    <label class={classes!("checkbox")}>
+                        <input
+                            type="checkbox"
+                            checked={config.budget_split.is_some()}
+                            onchange={on_custom_split_toggle}
+                        />
+                        {"Custom Split (otherwise a sixth for tools and a quarter for consumables)"}
+                    </label>
+                </div>
+
+                <div class={classes!("columns", "is-centered")}>
+                    <div class={classes!("column")}>
+                        <div class={classes!("field")}>
+                            <label class={classes!("label")}>{"Weapons %"}</label>
+                            <div class={classes!("control")}>
+                                <input
+                                    class={classes!("input", split_invalid.then_some("is-danger"))}
+                                    type="number"
+                                    min="0"
+                                    max="100"
+                                    placeholder={"Weapons %"}
+                                    value={budget_split.weapons.to_string()}
+                                    disabled={split_disabled}
+                                    oninput={on_budget_split_input(|config, percent| config.budget_split.get_or_insert_with(BudgetSplit::default).weapons = percent)}
+                                />
+                            </div>
+                        </div>
+                    </div>
+
+                    <div class={classes!("column")}>
+                        <div class={classes!("field")}>
+                            <label class={classes!("label")}>{"Tools %"}</label>
+                            <div class={classes!("control")}>
+                                <input
+                                    class={classes!("input", split_invalid.then_some("is-danger"))}
+                                    type="number"
+                                    min="0"
+                                    max="100"
+                                    placeholder={"Tools %"}
+                                    value={budget_split.tools.to_string()}
+                                    disabled={split_disabled}
+                                    oninput={on_budget_split_input(|config, percent| config.budget_split.get_or_insert_with(BudgetSplit::default).tools = percent)}
+                                />
+                            </div>
+                        </div>
+                    </div>
+
+                    <div class={classes!("column")}>
+                        <div class={classes!("field")}>
+                            <label class={classes!("label")}>{"Consumables %"}</label>
+                            <div class={classes!("control")}>
+                                <input
+                                    class={classes!("input", split_invalid.then_some("is-danger"))}
+                                    type="number"
+                                    min="0"
+                                    max="100"
+                                    placeholder={"Consumables %"}
+                                    value={budget_split.consumables.to_string()}
+                                    disabled={split_disabled}
+                                    oninput={on_budget_split_input(|config, percent| config.budget_split.get_or_insert_with(BudgetSplit::default).consumables = percent)}
+                                />
+                            </div>
+                        </div>
+                    </div>
+
+                    <div class={classes!("column")}>
+                        <div class={classes!("field")}>
+                            <label class={classes!("label")}>{"Leftovers"}</label>
+                            <div class={classes!("control")}>
+                                <div class={classes!("select")}>
+                                    <select onchange={on_leftover_policy_changed}>
+                                    <option
+                                        selected={config.leftover_policy == LeftoverPolicy::Cascade}
+                                        value={LeftoverPolicy::Cascade.to_string()}
+                                    >{"Cascade Leftovers"}</option>
+                                    <option
+                                        selected={config.leftover_policy == LeftoverPolicy::Pool}
+                                        value={LeftoverPolicy::Pool.to_string()}
+                                    >{"Pool Everything"}</option>
+                                    <option
+                                        selected={config.leftover_policy == LeftoverPolicy::Strict}
+                                        value={LeftoverPolicy::Strict.to_string()}
+                                    >{"Strict Caps"}</option>
+                                    </select>
+                                </div>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+
+                if split_invalid {
+                    <p class={classes!("help", "is-danger", "has-text-centered")}>
+                        {"The budget split has to add up to 100%."}
+                    </p>
+                }
+
+                <p class={classes!("subtitle", "has-text-centered")}>{"Weapon Ammo Chances"}</p>
+
+                <div class={classes!("columns", "is-centered")}>
+                    <div class={classes!("column")}>
+                        <div class={classes!("field")}>
+                            <label class={classes!("label")}>{"Long Ammo"}</label>
+                            <div class={classes!("control")}>
+                                <input
+                                    class={classes!("input")}
+                                    type="number"
+                                    min="0"
+                                    placeholder={"Long Ammo Chance"}
+                                    value={config.long_ammo_chance.to_string()}
+                                    oninput={on_ammo_chance_input(|config, chance| config.long_ammo_chance = chance)}
+                                />
+                            </div>
+                        </div>
+                    </div>
+
+                    <div class={classes!("column")}>
+                        <div class={classes!("field")}>
+                            <label class={classes!("label")}>{"Medium Ammo"}</label>
+                            <div class={classes!("control")}>
+                                <input
+                                    class={classes!("input")}
+                                    type="number"
+                                    min="0"
+                                    placeholder={"Medium Ammo Chance"}
+                                    value={config.medium_ammo_chance.to_string()}
+                                    oninput={on_ammo_chance_input(|config, chance| config.medium_ammo_chance = chance)}
+                                />
+                            </div>
+                        </div>
+                    </div>
+
+                    <div class={classes!("column")}>
+                        <div class={classes!("field")}>
+                            <label class={classes!("label")}>{"Compact Ammo"}</label>
+                            <div class={classes!("control")}>
+                                <input
+                                    class={classes!("input")}
+                                    type="number"
+                                    min="0"
+                                    placeholder={"Compact Ammo Chance"}
+                                    value={config.compact_ammo_chance.to_string()}
+                                    oninput={on_ammo_chance_input(|config, chance| config.compact_ammo_chance = chance)}
+                                />
+                            </div>
+                        </div>
+                    </div>
+                </div>
+
+                <p class={classes!("help", "has-text-centered")}>
+                    {"Shotguns, melee weapons and every other ammo size share the average of the three. "}
+                    {"A chance of 0 is never picked, with all three at 0 no weapon can be picked."}
+                </p>
+
                 <p class={classes!("subtitle", "has-text-centered")}>{"General Options"}</p>
 
                 <div class={classes!("columns")}>
@@ -405,6 +976,28 @@ pub fn AdvancedOptions(props: &AdvancedOptionsProps) -> Html {
                             {"Quartermaster"}
                         </label>
                     </div>
+
+                    <div class={classes!("column")}>
+                        <label class={classes!("checkbox")}>
+                            <input
+                                type="checkbox"
+                                checked={config.option_exists(ToggleOption::OnlyUnlocked)}
+                                onchange={on_only_unlocked_toggle}
+                            />
+                            {"Only Unlocked Items"}
+                        </label>
+                    </div>
+
+                    <div class={classes!("column")}>
+                        <label class={classes!("checkbox")}>
+                            <input
+                                type="checkbox"
+                                checked={config.option_exists(ToggleOption::BestValue)}
+                                onchange={on_best_value_toggle}
+                            />
+                            {"Best Value"}
+                        </label>
+                    </div>
                 </div>
 
                 <p class={classes!("subtitle", "has-text-centered")}>{"Always Toggles"}</p>
@@ -416,16 +1009,113 @@ pub fn AdvancedOptions(props: &AdvancedOptionsProps) -> Html {
                     <div class={classes!("column")}>{always_quartermaster_html}</div>
                 </div>
 
+                <p class={classes!("subtitle", "has-text-centered")}>{"Theme"}</p>
+
+                <div class={classes!("columns", "is-centered")}>
+                    <div class={classes!("column")}>
+                        <div class={classes!("field")}>
+                            <label class={classes!("label")}>{"Build"}</label>
+                            <div class={classes!("control")}>
+                                <div class={classes!("select")}>
+                                    <select onchange={on_theme_changed}>
+                                    <option
+                                        selected={config.theme == Theme::NoTheme}
+                                        value={Theme::NoTheme.to_string()}
+                                    >{"No Theme"}</option>
+                                    <option
+                                        selected={config.theme == Theme::Silent}
+                                        value={Theme::Silent.to_string()}
+                                    >{"All Silent"}</option>
+                                    <option
+                                        selected={config.theme == Theme::Fire}
+                                        value={Theme::Fire.to_string()}
+                                    >{"Fire Build"}</option>
+                                    <option
+                                        selected={config.theme == Theme::Poison}
+                                        value={Theme::Poison.to_string()}
+                                    >{"Poison Build"}</option>
+                                    <option
+                                        selected={config.theme == Theme::Explosives}
+                                        value={Theme::Explosives.to_string()}
+                                    >{"Explosives Only"}</option>
+                                    </select>
+                                </div>
+                            </div>
+                        </div>
+                    </div>
+
+                    <div class={classes!("column")}>
+                        <div class={classes!("field")}>
+                            <label class={classes!("label")}>{"When Nothing Fits"}</label>
+                            <div class={classes!("control")}>
+                                <div class={classes!("select")}>
+                                    <select
+                                        disabled={config.theme == Theme::NoTheme}
+                                        onchange={on_theme_fallback_changed}
+                                    >
+                                    <option
+                                        selected={config.theme_fallback == ThemeFallback::AnyItem}
+                                        value={ThemeFallback::AnyItem.to_string()}
+                                    >{"Pick Anything"}</option>
+                                    <option
+                                        selected={config.theme_fallback == ThemeFallback::Empty}
+                                        value={ThemeFallback::Empty.to_string()}
+                                    >{"Leave Empty"}</option>
+                                    </select>
+                                </div>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+
+                <p class={classes!("subtitle", "has-text-centered")}>{"Weapon Preferences"}</p>
+
+                <div class={classes!("columns")}>
+                    {weapon_preferences_html}
+                </div>
+
                 <p class={classes!("subtitle", "has-text-centered")}>{"Tool Preferences"}</p>
 
                 <div class={classes!("columns")}>
                     {tool_preferences_html}
                 </div>
 
+                <p class={classes!("subtitle", "has-text-centered")}>{"Consumable Preferences"}</p>
+
+                <div class={classes!("columns")}>
+                    {consumable_preferences_html}
+                </div>
+
+                <p class={classes!("subtitle", "has-text-centered")}>{"Excluded Items"}</p>
+
+                <ExclusionList
+                    excluded_items={config.excluded_items.clone()}
+                    excluded_ammo={config.excluded_ammo.clone()}
+                    on_item_toggle={on_excluded_item_toggle}
+                    on_ammo_toggle={on_excluded_ammo_toggle}
+                />
+
+                <p class={classes!("subtitle", "has-text-centered")}>{"Required Items"}</p>
+
+                <RequiredItems
+                    required_items={config.required_items.clone()}
+                    required_groups={config.required_groups.clone()}
+                    on_required_change={on_required_items_change}
+                    on_groups_change={on_required_groups_change}
+                />
+
+                <p class={classes!("subtitle", "has-text-centered")}>{"Item Limits"}</p>
+
+                <ItemLimits
+                    item_limits={config.item_limits.clone()}
+                    on_limits_change={on_item_limits_change}
+                />
+
                 <div class={classes!("columns", "is-centered")}>
                     <div class={classes!("column", "is-flex-grow-0")}>
                         <button
                             class={classes!("button", "is-primary")}
+                            disabled={split_invalid}
                             onclick={on_options_close_click.clone()}
                         >{"Confirm"}</button>
                     </div>
@@ -1,6 +1,6 @@
 use yew::prelude::*;
 
-use crate::randomizer::budget::Transaction;
+use crate::randomizer::budget::TransactionCategory;
 use crate::randomizer::Budget;
 
 #[derive(PartialEq, Eq, Properties)]
@@ -12,36 +12,46 @@ pub struct BudgetDisplayProps {
 pub fn BudgetDisplay(props: &BudgetDisplayProps) -> Html {
     let BudgetDisplayProps { budget } = props;
 
-    let transactions_html = budget.debug_transactions
+    let transactions_html = budget
+        .transactions
         .iter()
-        .map(|transaction| match transaction {
-            Transaction::Tool(refund, amount, name)
-                | Transaction::Consumable(refund, amount, name)
-                | Transaction::Weapon(refund, amount, name) => html! {
+        .map(|transaction| {
+            let name = if transaction.category == TransactionCategory::Bullet {
+                format!("{} Ammo", transaction.item)
+            } else {
+                transaction.item.clone()
+            };
+
+            html! {
                 <p>
-                    if *refund {
+                    if transaction.is_refund() {
                         <span class={classes!("has-text-danger")}>{&format!("Refund {name}")}</span>
-                        <span>{&format!("-{amount}")}</span>
                     } else {
                         <span class={classes!("has-text-success")}>{&format!("Purchase {name}")}</span>
-                        <span>{amount}</span>
-                    }
-                </p>
-            },
-            Transaction::Bullet(refund, amount, name) => html! {
-                <p>
-                    if *refund {
-                        <span class={classes!("has-text-danger")}>{&format!("Refund {name} Ammo")}</span>
-                        <span>{&format!("-{amount}")}</span>
-                    } else {
-                        <span class={classes!("has-text-success")}>{&format!("Purchase {name} Ammo")}</span>
-                        <span>{amount}</span>
                     }
+                    <span>{transaction.amount}</span>
                 </p>
             }
         })
         .collect::<Html>();
 
+    let subtotals_html = [
+        TransactionCategory::Weapon,
+        TransactionCategory::Bullet,
+        TransactionCategory::Tool,
+        TransactionCategory::Consumable,
+    ]
+    .into_iter()
+    .map(|category| {
+        html! {
+            <p>
+                <span>{&format!("{category}")}</span>
+                <span>{budget.get_subtotal(category)}</span>
+            </p>
+        }
+    })
+    .collect::<Html>();
+
     html! {
         <div class={classes!("transactions-popup")}>
             <h3 class={classes!("is-size-3", "has-text-centered")}>
@@ -50,8 +60,12 @@ pub fn BudgetDisplay(props: &BudgetDisplayProps) -> Html {
 
             {transactions_html}
 
+            <hr />
+
+            {subtotals_html}
+
             <h5 class={classes!("is-size-5", "has-text-centered")}>
-                {&format!("Amount Left Over: {}", budget.initial_budget - budget.total_cost)}
+                {&format!("Amount Left Over: {}", budget.initial_budget.saturating_sub(budget.total_cost))}
             </h5>
 
             <h3 class={classes!("is-size-3", "has-text-centered")}>
@@ -1,6 +1,8 @@
 use yew::prelude::*;
 
+use crate::components::SlotErrors;
 use crate::content::GenericItem;
+use crate::randomizer::loadout::LoadoutError;
 use crate::TRANSPARENT_B64;
 
 #[derive(PartialEq, Properties)]
@@ -8,6 +10,8 @@ pub struct ConsumableSlotProps {
     pub consumable: Option<GenericItem>,
     pub locked: bool,
     pub id: usize,
+    // Failed purchases for this slot.
+    pub errors: Vec<LoadoutError>,
     pub on_consumable_slot_clicked: Callback<usize>,
     pub on_consumable_toggle_lock: Callback<usize>,
     pub on_consumable_delete: Callback<usize>,
@@ -20,6 +24,7 @@ pub fn ConsumableSlot(props: &ConsumableSlotProps) -> Html {
         consumable,
         locked,
         id,
+        errors,
         on_consumable_slot_clicked,
         on_consumable_toggle_lock,
         on_consumable_delete,
@@ -83,6 +88,7 @@ pub fn ConsumableSlot(props: &ConsumableSlotProps) -> Html {
                     onclick={on_consumable_clicked_handle}
                 />
                 <span class={classes!("consumable-name")}>{consumable.name}</span>
+                <SlotErrors errors={errors.clone()} />
             </div>
         }
     } else {
@@ -99,6 +105,7 @@ pub fn ConsumableSlot(props: &ConsumableSlotProps) -> Html {
                     alt={""}
                     onclick={on_consumable_clicked_handle}
                 />
+                <SlotErrors errors={errors.clone()} />
             </div>
         }
     }
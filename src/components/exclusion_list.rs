@@ -0,0 +1,114 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::prelude::*;
+
+use crate::content::{BulletVariant, GenericItem, CORE_SEARCH_UTIL};
+use crate::randomizer::unlocks::UnlockKey;
+
+#[derive(PartialEq, Properties)]
+pub struct ExclusionListProps {
+    pub excluded_items: Vec<UnlockKey>,
+    pub excluded_ammo: Vec<BulletVariant>,
+    pub on_item_toggle: Callback<UnlockKey>,
+    pub on_ammo_toggle: Callback<BulletVariant>,
+}
+
+#[function_component]
+pub fn ExclusionList(props: &ExclusionListProps) -> Html {
+    let ExclusionListProps {
+        excluded_items,
+        excluded_ammo,
+        on_item_toggle,
+        on_ammo_toggle,
+    } = props;
+
+    let search_terms_handle = use_state(String::new);
+    let search_terms = (*search_terms_handle).to_lowercase();
+
+    let on_search_field_input = move |e: InputEvent| {
+        let target: Option<EventTarget> = e.target();
+
+        let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+        if let Some(input) = input {
+            search_terms_handle.set(input.value());
+        }
+    };
+
+    let items_html = |items: &[GenericItem]| {
+        items
+            .iter()
+            .filter(|item| item.to_full_name().to_lowercase().contains(&search_terms))
+            .map(|item| {
+                let key = UnlockKey::from_item(item);
+                let excluded = excluded_items.contains(&key);
+
+                let on_item_change = {
+                    let on_item_toggle = on_item_toggle.clone();
+
+                    move |_| {
+                        on_item_toggle.emit(key.clone());
+                    }
+                };
+
+                html! {
+                    <label class={classes!("checkbox")}>
+                        <input type="checkbox" checked={excluded} onchange={on_item_change} />
+                        {item.to_full_name()}
+                    </label>
+                }
+            })
+            .collect::<Html>()
+    };
+
+    let ammo_html = CORE_SEARCH_UTIL
+        .get_ammo_variants()
+        .into_iter()
+        .filter(|ammo| ammo.to_string().to_lowercase().contains(&search_terms))
+        .map(|ammo| {
+            let excluded = excluded_ammo.contains(ammo);
+
+            let on_ammo_change = {
+                let on_ammo_toggle = on_ammo_toggle.clone();
+                let ammo = ammo.clone();
+
+                move |_| {
+                    on_ammo_toggle.emit(ammo.clone());
+                }
+            };
+
+            html! {
+                <label class={classes!("checkbox")}>
+                    <input type="checkbox" checked={excluded} onchange={on_ammo_change} />
+                    {format!("{ammo} Ammo")}
+                </label>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+            <input
+                class={classes!("input")}
+                type="text"
+                placeholder="Search"
+                oninput={on_search_field_input}
+            />
+
+            <div class={classes!("columns")}>
+                <div class={classes!("column", "unlocks-list")}>
+                    {items_html(&CORE_SEARCH_UTIL.weapons)}
+                </div>
+                <div class={classes!("column", "unlocks-list")}>
+                    {items_html(&CORE_SEARCH_UTIL.tools)}
+                </div>
+                <div class={classes!("column", "unlocks-list")}>
+                    {items_html(&CORE_SEARCH_UTIL.consumables)}
+                </div>
+                <div class={classes!("column", "unlocks-list")}>
+                    {ammo_html}
+                </div>
+            </div>
+        </>
+    }
+}
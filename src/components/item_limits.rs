@@ -0,0 +1,161 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::randomizer::limits::{self, ItemLimit};
+
+#[derive(PartialEq, Properties)]
+pub struct ItemLimitsProps {
+    pub item_limits: Vec<ItemLimit>,
+    pub on_limits_change: Callback<Vec<ItemLimit>>,
+}
+
+#[function_component]
+pub fn ItemLimits(props: &ItemLimitsProps) -> Html {
+    let ItemLimitsProps {
+        item_limits,
+        on_limits_change,
+    } = props;
+
+    let categories = limits::limit_categories();
+
+    let on_add_limit_click = {
+        let on_limits_change = on_limits_change.clone();
+        let item_limits = item_limits.clone();
+
+        move |_| {
+            let mut item_limits = item_limits.clone();
+            item_limits.push(ItemLimit::default());
+            on_limits_change.emit(item_limits);
+        }
+    };
+
+    let limits_html = item_limits
+        .iter()
+        .enumerate()
+        .map(|(pos, limit)| {
+            // The select value is the category's position in `limits::limit_categories`.
+            let on_category_change = {
+                let on_limits_change = on_limits_change.clone();
+                let item_limits = item_limits.clone();
+                let categories = categories.clone();
+
+                move |e: Event| {
+                    let target: Option<EventTarget> = e.target();
+
+                    let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+
+                    let category = select
+                        .and_then(|select| select.value().parse::<usize>().ok())
+                        .and_then(|index| categories.get(index));
+
+                    if let Some(category) = category {
+                        let mut item_limits = item_limits.clone();
+                        item_limits[pos].category = category.clone();
+                        on_limits_change.emit(item_limits);
+                    }
+                }
+            };
+
+            let on_min_input = {
+                let on_limits_change = on_limits_change.clone();
+                let item_limits = item_limits.clone();
+
+                move |e: InputEvent| {
+                    let target: Option<EventTarget> = e.target();
+
+                    let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+                    if let Some(input) = input {
+                        let mut item_limits = item_limits.clone();
+                        item_limits[pos].min = input.value().parse::<u8>().unwrap_or(0);
+                        on_limits_change.emit(item_limits);
+                    }
+                }
+            };
+
+            let on_max_input = {
+                let on_limits_change = on_limits_change.clone();
+                let item_limits = item_limits.clone();
+
+                move |e: InputEvent| {
+                    let target: Option<EventTarget> = e.target();
+
+                    let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+                    if let Some(input) = input {
+                        let mut item_limits = item_limits.clone();
+                        item_limits[pos].max = input.value().parse::<u8>().ok();
+                        on_limits_change.emit(item_limits);
+                    }
+                }
+            };
+
+            let on_remove_click = {
+                let on_limits_change = on_limits_change.clone();
+                let item_limits = item_limits.clone();
+
+                move |_| {
+                    let mut item_limits = item_limits.clone();
+                    item_limits.remove(pos);
+                    on_limits_change.emit(item_limits);
+                }
+            };
+
+            let options_html = categories
+                .iter()
+                .enumerate()
+                .map(|(index, category)| {
+                    html! {
+                        <option
+                            selected={*category == limit.category}
+                            value={index.to_string()}
+                        >{category.to_string()}</option>
+                    }
+                })
+                .collect::<Html>();
+
+            html! {
+                <div class={classes!("columns", "is-mobile", "is-vcentered")}>
+                    <div class={classes!("column")}>
+                        <div class={classes!("select")}>
+                            <select onchange={on_category_change}>
+                                {options_html}
+                            </select>
+                        </div>
+                    </div>
+                    <div class={classes!("column")}>
+                        <input
+                            class={classes!("input")}
+                            type="number"
+                            min="0"
+                            placeholder={"Min"}
+                            value={limit.min.to_string()}
+                            oninput={on_min_input}
+                        />
+                    </div>
+                    <div class={classes!("column")}>
+                        <input
+                            class={classes!("input")}
+                            type="number"
+                            min="0"
+                            placeholder={"Max"}
+                            value={limit.max.map_or_else(String::new, |max| max.to_string())}
+                            oninput={on_max_input}
+                        />
+                    </div>
+                    <div class={classes!("column", "is-narrow")}>
+                        <button class={classes!("delete")} onclick={on_remove_click}></button>
+                    </div>
+                </div>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+            {limits_html}
+            <button class="button" onclick={on_add_limit_click}>{"Add Limit"}</button>
+        </>
+    }
+}
@@ -7,6 +7,7 @@ use crate::content::{GenericItem, ItemVariant, CORE_SEARCH_UTIL};
 #[derive(PartialEq, Properties)]
 pub struct ItemDisplayProps {
     pub item: GenericItem,
+    pub max_rank: u8,
     pub on_item_clicked: Callback<GenericItem>,
 }
 
@@ -14,9 +15,18 @@ pub struct ItemDisplayProps {
 pub fn ItemDisplay(props: &ItemDisplayProps) -> Html {
     let ItemDisplayProps {
         item,
+        max_rank,
         on_item_clicked,
     } = props;
 
+    // Items above the bloodline rank can still be picked manually, they are only flagged.
+    let rank = CORE_SEARCH_UTIL.get_item_rank(item);
+    let above_rank = if rank > *max_rank {
+        Some("above-rank")
+    } else {
+        None
+    };
+
     let on_item_clicked_cb = {
         let on_item_clicked_handle = on_item_clicked.clone();
         let item_clone = item.clone();
@@ -27,9 +37,13 @@ pub fn ItemDisplay(props: &ItemDisplayProps) -> Html {
     };
 
     html! {
-        <div class={classes!("item-display")} onclick={on_item_clicked_cb}>
+        <div class={classes!("item-display", above_rank)} onclick={on_item_clicked_cb}>
             <img class={classes!("item-img")} src={item.to_image_path()} alt={item.name.clone()} />
             <span class={classes!("item-name")}>{item.to_full_name()}</span>
+
+            if above_rank.is_some() {
+                <span class={classes!("item-rank", "has-text-danger")}>{&format!("Rank {rank}")}</span>
+            }
         </div>
     }
 }
@@ -39,6 +53,7 @@ pub struct ItemSelectListProps {
     pub id: usize,
     pub items: Vec<GenericItem>,
     pub item_variant: ItemVariant,
+    pub max_rank: u8,
     pub on_item_selected: Callback<(Option<GenericItem>, usize)>,
 }
 
@@ -48,10 +63,12 @@ pub fn ItemSelectList(props: &ItemSelectListProps) -> Html {
         id,
         items,
         item_variant,
+        max_rank,
         on_item_selected,
     } = props;
 
     let id = *id;
+    let max_rank = *max_rank;
 
     let dual_wield_handle = use_state(|| false);
     let dual_wield = *dual_wield_handle;
@@ -222,7 +239,7 @@ pub fn ItemSelectList(props: &ItemSelectListProps) -> Html {
                     };
 
                     html! {
-                        <ItemDisplay item={item.clone()} on_item_clicked={on_item_clicked} />
+                        <ItemDisplay item={item.clone()} {max_rank} on_item_clicked={on_item_clicked} />
                     }
                 }).collect::<Html>()}
             </div>
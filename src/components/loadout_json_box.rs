@@ -0,0 +1,92 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlTextAreaElement};
+use yew::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct LoadoutJsonBoxProps {
+    // The current loadout from `randomizer::loadout_json::export`.
+    pub export: String,
+    pub problems: Vec<String>,
+    pub on_import: Callback<String>,
+}
+
+#[function_component]
+pub fn LoadoutJsonBox(props: &LoadoutJsonBoxProps) -> Html {
+    let LoadoutJsonBoxProps {
+        export,
+        problems,
+        on_import,
+    } = props;
+
+    let is_open_handle = use_state(|| false);
+    let is_open = *is_open_handle;
+
+    let text_handle = use_state(String::new);
+    let text = (*text_handle).clone();
+
+    let on_toggle_click = move |_| {
+        is_open_handle.set(!is_open);
+    };
+
+    let on_text_input = {
+        let text_handle = text_handle.clone();
+
+        move |e: InputEvent| {
+            let target: Option<EventTarget> = e.target();
+
+            let textarea = target.and_then(|t| t.dyn_into::<HtmlTextAreaElement>().ok());
+
+            if let Some(textarea) = textarea {
+                text_handle.set(textarea.value());
+            }
+        }
+    };
+
+    let on_export_click = {
+        let export = export.clone();
+
+        move |_| {
+            text_handle.set(export.clone());
+        }
+    };
+
+    let on_import_click = {
+        let on_import = on_import.clone();
+        let text = text.clone();
+
+        move |_| {
+            on_import.emit(text.clone());
+        }
+    };
+
+    let problems_html = problems
+        .iter()
+        .map(|problem| html! { <p class={classes!("has-text-danger")}>{problem}</p> })
+        .collect::<Html>();
+
+    html! {
+        <div class={classes!("loadout-json", "has-text-centered")}>
+            <button class={classes!("button", "is-small")} onclick={on_toggle_click}>
+                {"Import / Export JSON"}
+            </button>
+
+            if is_open {
+                <textarea
+                    class={classes!("textarea", "my-2")}
+                    placeholder={"Paste an exported loadout here"}
+                    value={text}
+                    oninput={on_text_input}
+                />
+
+                <div class={classes!("buttons", "is-centered")}>
+                    <button class={classes!("button")} onclick={on_export_click}>{"Export"}</button>
+                    <button class={classes!("button", "is-primary")} onclick={on_import_click}>
+                        {"Import"}
+                    </button>
+                </div>
+            }
+
+            {problems_html}
+        </div>
+    }
+}
@@ -2,16 +2,32 @@ pub mod advanced_options;
 pub mod budget_display;
 pub mod bullet_select_list;
 pub mod consumable_slot;
+pub mod exclusion_list;
+pub mod item_limits;
 pub mod item_select_list;
+pub mod loadout_json_box;
 pub mod nav;
+pub mod preset_manager;
+pub mod required_items;
+pub mod slot_errors;
+pub mod squad_hunter;
 pub mod tool_slot;
+pub mod unlock_tracker;
 pub mod weapon_slot;
 
 pub use advanced_options::AdvancedOptions;
 pub use budget_display::BudgetDisplay;
 pub use bullet_select_list::BulletSelectList;
 pub use consumable_slot::ConsumableSlot;
+pub use exclusion_list::ExclusionList;
+pub use item_limits::ItemLimits;
 pub use item_select_list::ItemSelectList;
+pub use loadout_json_box::LoadoutJsonBox;
 pub use nav::Nav;
+pub use preset_manager::PresetManager;
+pub use required_items::RequiredItems;
+pub use slot_errors::SlotErrors;
+pub use squad_hunter::SquadHunter;
 pub use tool_slot::ToolSlot;
+pub use unlock_tracker::UnlockTracker;
 pub use weapon_slot::WeaponSlot;
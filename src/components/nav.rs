@@ -37,6 +37,12 @@ pub fn Nav() -> Html {
                     <Link<Route> classes={classes!("navbar-item")} to={Route::Home}>
                         { "Home" }
                     </Link<Route>>
+                    <Link<Route> classes={classes!("navbar-item")} to={Route::Squad}>
+                        { "Squad" }
+                    </Link<Route>>
+                    <Link<Route> classes={classes!("navbar-item")} to={Route::UnlockPlanner}>
+                        { "Unlock Planner" }
+                    </Link<Route>>
                 </div>
             </div>
         </nav>
@@ -0,0 +1,218 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::randomizer::{
+    preset::{self, Preset},
+    Config,
+};
+use crate::storage;
+
+#[derive(PartialEq, Properties)]
+pub struct PresetManagerProps {
+    pub config: Config,
+    pub on_preset_apply: Callback<Config>,
+}
+
+#[function_component]
+pub fn PresetManager(props: &PresetManagerProps) -> Html {
+    let PresetManagerProps {
+        config,
+        on_preset_apply,
+    } = props;
+
+    let built_in = preset::built_in_presets();
+
+    let saved_handle = use_state(|| {
+        storage::load::<Vec<serde_json::Value>>(storage::PRESETS_KEY)
+            .map(preset::readable_presets)
+            .unwrap_or_default()
+    });
+    let saved = (*saved_handle).clone();
+
+    // Position in the built-in presets followed by the saved ones.
+    let selected_handle = use_state(|| 0_usize);
+    let selected = *selected_handle;
+    let saved_pos = selected.checked_sub(built_in.len());
+
+    let name_handle = use_state(String::new);
+    let name = (*name_handle).clone();
+
+    let error_handle = use_state(|| None::<&'static str>);
+    let error = *error_handle;
+
+    // Runs a change against the saved presets, only storing them when it worked.
+    let update_saved = {
+        let error_handle = error_handle.clone();
+        let saved = saved.clone();
+
+        move |change: &dyn Fn(&mut Vec<Preset>) -> Result<(), &'static str>| -> bool {
+            let mut saved = saved.clone();
+
+            match change(&mut saved) {
+                Ok(()) => {
+                    storage::save(storage::PRESETS_KEY, &saved);
+                    saved_handle.set(saved);
+                    error_handle.set(None);
+                    true
+                }
+                Err(error) => {
+                    error_handle.set(Some(error));
+                    false
+                }
+            }
+        }
+    };
+
+    let on_preset_changed = {
+        let selected_handle = selected_handle.clone();
+
+        move |e: Event| {
+            let target: Option<EventTarget> = e.target();
+
+            let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+
+            if let Some(select) = select {
+                if let Ok(pos) = select.value().parse::<usize>() {
+                    selected_handle.set(pos);
+                }
+            }
+        }
+    };
+
+    let on_name_input = {
+        move |e: InputEvent| {
+            let target: Option<EventTarget> = e.target();
+
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+            if let Some(input) = input {
+                name_handle.set(input.value());
+            }
+        }
+    };
+
+    let on_apply_click = {
+        let on_preset_apply = on_preset_apply.clone();
+        let config = config.clone();
+        let preset = built_in.iter().chain(saved.iter()).nth(selected).cloned();
+
+        move |_| {
+            if let Some(preset) = &preset {
+                on_preset_apply.emit(preset.apply(&config));
+            }
+        }
+    };
+
+    let on_save_click = {
+        let update_saved = update_saved.clone();
+        let selected_handle = selected_handle.clone();
+        let built_in_len = built_in.len();
+        let config = config.clone();
+        let name = name.clone();
+        // Saving over an existing preset keeps its place, a new one goes on the end.
+        let pos = saved
+            .iter()
+            .position(|check| check.name.eq_ignore_ascii_case(name.trim()))
+            .unwrap_or(saved.len());
+
+        move |_| {
+            if update_saved(&|saved| preset::save_preset(saved, &name, &config)) {
+                selected_handle.set(built_in_len + pos);
+            }
+        }
+    };
+
+    let on_rename_click = {
+        let update_saved = update_saved.clone();
+        let name = name.clone();
+
+        move |_| {
+            if let Some(pos) = saved_pos {
+                update_saved(&|saved| preset::rename_preset(saved, pos, &name));
+            }
+        }
+    };
+
+    let on_delete_click = {
+        move |_| {
+            if let Some(pos) = saved_pos {
+                if update_saved(&|saved| preset::delete_preset(saved, pos)) {
+                    selected_handle.set(0);
+                }
+            }
+        }
+    };
+
+    let options_html = built_in
+        .iter()
+        .map(|preset| format!("{} (Built-in)", preset.name))
+        .chain(saved.iter().map(|preset| preset.name.clone()))
+        .enumerate()
+        .map(|(pos, name)| {
+            html! {
+                <option selected={pos == selected} value={pos.to_string()}>{name}</option>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+        <div class={classes!("columns", "is-centered")}>
+            <div class={classes!("column")}>
+                <div class={classes!("field", "has-addons")}>
+                    <div class={classes!("control", "is-expanded")}>
+                        <div class={classes!("select", "is-fullwidth")}>
+                            <select onchange={on_preset_changed}>
+                                {options_html}
+                            </select>
+                        </div>
+                    </div>
+                    <div class={classes!("control")}>
+                        <button class={classes!("button", "is-primary")} onclick={on_apply_click}>
+                            {"Apply"}
+                        </button>
+                    </div>
+                    <div class={classes!("control")}>
+                        <button
+                            class={classes!("button", "is-danger")}
+                            disabled={saved_pos.is_none()}
+                            onclick={on_delete_click}
+                        >{"Delete"}</button>
+                    </div>
+                </div>
+            </div>
+
+            <div class={classes!("column")}>
+                <div class={classes!("field", "has-addons")}>
+                    <div class={classes!("control", "is-expanded")}>
+                        <input
+                            class={classes!("input", error.map(|_| "is-danger"))}
+                            type="text"
+                            placeholder={"Preset Name"}
+                            value={name}
+                            oninput={on_name_input}
+                        />
+                    </div>
+                    <div class={classes!("control")}>
+                        <button class={classes!("button")} onclick={on_save_click}>
+                            {"Save Current"}
+                        </button>
+                    </div>
+                    <div class={classes!("control")}>
+                        <button
+                            class={classes!("button")}
+                            disabled={saved_pos.is_none()}
+                            onclick={on_rename_click}
+                        >{"Rename"}</button>
+                    </div>
+                </div>
+            </div>
+        </div>
+
+        if let Some(error) = error {
+            <p class={classes!("help", "is-danger", "has-text-centered")}>{error}</p>
+        }
+        </>
+    }
+}
@@ -0,0 +1,223 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::prelude::*;
+
+use crate::content::CORE_SEARCH_UTIL;
+use crate::randomizer::unlocks::UnlockKey;
+
+// Keeps the search results short enough to fit in the options modal.
+const MAX_SEARCH_RESULTS: usize = 10;
+
+#[derive(PartialEq, Properties)]
+pub struct RequiredItemsProps {
+    pub required_items: Vec<UnlockKey>,
+    pub required_groups: Vec<Vec<UnlockKey>>,
+    pub on_required_change: Callback<Vec<UnlockKey>>,
+    pub on_groups_change: Callback<Vec<Vec<UnlockKey>>>,
+}
+
+#[function_component]
+pub fn RequiredItems(props: &RequiredItemsProps) -> Html {
+    let RequiredItemsProps {
+        required_items,
+        required_groups,
+        on_required_change,
+        on_groups_change,
+    } = props;
+
+    let search_terms_handle = use_state(String::new);
+    let search_terms = (*search_terms_handle).to_lowercase();
+
+    let on_search_field_input = move |e: InputEvent| {
+        let target: Option<EventTarget> = e.target();
+
+        let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+        if let Some(input) = input {
+            search_terms_handle.set(input.value());
+        }
+    };
+
+    let on_new_group_click = {
+        let on_groups_change = on_groups_change.clone();
+        let required_groups = required_groups.clone();
+
+        move |_| {
+            let mut required_groups = required_groups.clone();
+            required_groups.push(vec![]);
+            on_groups_change.emit(required_groups);
+        }
+    };
+
+    let results_html = if search_terms.is_empty() {
+        html! {}
+    } else {
+        CORE_SEARCH_UTIL
+            .weapons
+            .iter()
+            .chain(&CORE_SEARCH_UTIL.tools)
+            .chain(&CORE_SEARCH_UTIL.consumables)
+            .filter(|item| item.to_full_name().to_lowercase().contains(&search_terms))
+            .take(MAX_SEARCH_RESULTS)
+            .map(|item| {
+                let key = UnlockKey::from_item(item);
+
+                let on_require_click = {
+                    let on_required_change = on_required_change.clone();
+                    let required_items = required_items.clone();
+                    let key = key.clone();
+
+                    move |_| {
+                        let mut required_items = required_items.clone();
+                        required_items.push(key.clone());
+                        on_required_change.emit(required_items);
+                    }
+                };
+
+                // Goes in the last group, starting one if there are none.
+                let on_add_to_group_click = {
+                    let on_groups_change = on_groups_change.clone();
+                    let required_groups = required_groups.clone();
+
+                    move |_| {
+                        let mut required_groups = required_groups.clone();
+
+                        if let Some(group) = required_groups.last_mut() {
+                            group.push(key.clone());
+                        } else {
+                            required_groups.push(vec![key.clone()]);
+                        }
+
+                        on_groups_change.emit(required_groups);
+                    }
+                };
+
+                html! {
+                    <div class={classes!("level", "is-mobile", "mb-1")}>
+                        <div class={classes!("level-left")}>{item.to_full_name()}</div>
+                        <div class={classes!("level-right", "buttons")}>
+                            <button class={classes!("button", "is-small")} onclick={on_require_click}>
+                                {"Require"}
+                            </button>
+                            <button class={classes!("button", "is-small")} onclick={on_add_to_group_click}>
+                                {"Add to Group"}
+                            </button>
+                        </div>
+                    </div>
+                }
+            })
+            .collect::<Html>()
+    };
+
+    let required_html = required_items
+        .iter()
+        .enumerate()
+        .map(|(pos, key)| {
+            let on_remove_click = {
+                let on_required_change = on_required_change.clone();
+                let required_items = required_items.clone();
+
+                move |_| {
+                    let mut required_items = required_items.clone();
+                    required_items.remove(pos);
+                    on_required_change.emit(required_items);
+                }
+            };
+
+            html! {
+                <div class={classes!("level", "is-mobile", "mb-1")}>
+                    <div class={classes!("level-left")}>{key.to_string()}</div>
+                    <div class={classes!("level-right")}>
+                        <button class={classes!("delete")} onclick={on_remove_click}></button>
+                    </div>
+                </div>
+            }
+        })
+        .collect::<Html>();
+
+    let groups_html = required_groups
+        .iter()
+        .enumerate()
+        .map(|(group_pos, group)| {
+            let members_html = group
+                .iter()
+                .enumerate()
+                .map(|(pos, key)| {
+                    let on_remove_click = {
+                        let on_groups_change = on_groups_change.clone();
+                        let required_groups = required_groups.clone();
+
+                        move |_| {
+                            let mut required_groups = required_groups.clone();
+                            required_groups[group_pos].remove(pos);
+                            on_groups_change.emit(required_groups);
+                        }
+                    };
+
+                    html! {
+                        <div class={classes!("level", "is-mobile", "mb-1")}>
+                            <div class={classes!("level-left")}>{key.to_string()}</div>
+                            <div class={classes!("level-right")}>
+                                <button class={classes!("delete")} onclick={on_remove_click}></button>
+                            </div>
+                        </div>
+                    }
+                })
+                .collect::<Html>();
+
+            let on_remove_group_click = {
+                let on_groups_change = on_groups_change.clone();
+                let required_groups = required_groups.clone();
+
+                move |_| {
+                    let mut required_groups = required_groups.clone();
+                    required_groups.remove(group_pos);
+                    on_groups_change.emit(required_groups);
+                }
+            };
+
+            html! {
+                <div class={classes!("box")}>
+                    <div class={classes!("level", "is-mobile")}>
+                        <div class={classes!("level-left", "has-text-weight-bold")}>
+                            {format!("One Of (Group {})", group_pos + 1)}
+                        </div>
+                        <div class={classes!("level-right")}>
+                            <button class={classes!("button", "is-small")} onclick={on_remove_group_click}>
+                                {"Remove Group"}
+                            </button>
+                        </div>
+                    </div>
+                    {members_html}
+                </div>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+            <input
+                class={classes!("input")}
+                type="text"
+                placeholder="Search"
+                oninput={on_search_field_input}
+            />
+
+            <div class={classes!("unlocks-list", "my-2")}>
+                {results_html}
+            </div>
+
+            <div class={classes!("columns")}>
+                <div class={classes!("column")}>
+                    <p class={classes!("has-text-weight-bold")}>{"Always Include"}</p>
+                    {required_html}
+                </div>
+
+                <div class={classes!("column")}>
+                    {groups_html}
+                    <button class="button" onclick={on_new_group_click}>{"New Group"}</button>
+                </div>
+            </div>
+        </>
+    }
+}
@@ -0,0 +1,25 @@
+use yew::prelude::*;
+
+use crate::randomizer::loadout::LoadoutError;
+
+#[derive(PartialEq, Eq, Properties)]
+pub struct SlotErrorsProps {
+    pub errors: Vec<LoadoutError>,
+}
+
+#[function_component]
+pub fn SlotErrors(props: &SlotErrorsProps) -> Html {
+    let SlotErrorsProps { errors } = props;
+
+    if errors.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class={classes!("slot-errors")}>
+            {errors.iter().map(|error| html! {
+                <p class={classes!("has-text-danger")}>{error.to_string()}</p>
+            }).collect::<Html>()}
+        </div>
+    }
+}
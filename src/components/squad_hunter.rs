@@ -0,0 +1,148 @@
+use std::fmt::Write;
+use yew::prelude::*;
+
+use crate::components::SlotErrors;
+use crate::content::generic_item::GenericItemLockable;
+use crate::randomizer::{
+    budget::TransactionCategory, loadout::LoadoutError, loadout_text, Budget, Loadout,
+};
+
+#[derive(PartialEq, Properties)]
+pub struct SquadHunterProps {
+    pub id: usize,
+    pub hunter: Loadout,
+    pub budget: Budget,
+    pub on_toggle_lock: Callback<(usize, TransactionCategory, u8)>,
+}
+
+fn slot_html(
+    hunter: &Loadout,
+    lockable: &GenericItemLockable,
+    category: TransactionCategory,
+    slot: u8,
+    on_lock_click: Callback<MouseEvent>,
+) -> Html {
+    let (lock_src, lock_alt) = if lockable.locked {
+        ("/images/icons/Lock.svg", "Locked")
+    } else {
+        ("/images/icons/Unlock.svg", "Unlocked")
+    };
+
+    let name = lockable.item.as_ref().map_or_else(
+        || "Empty".to_string(),
+        |item| {
+            let mut name = item.to_full_name().trim().to_string();
+
+            if item.dual_wield {
+                name.push_str(" (Dual Wield)");
+            }
+
+            if !item.ammo_equipped.is_empty() {
+                let ammo = item
+                    .ammo_equipped
+                    .iter()
+                    .map(loadout_text::ammo_name)
+                    .collect::<Vec<String>>();
+                let _ = write!(name, " [{}]", ammo.join(", "));
+            }
+
+            name
+        },
+    );
+
+    html! {
+        <div class={classes!("squad-slot")}>
+            <p class={classes!("item-actions")}>
+                <img src={lock_src} alt={lock_alt} onclick={on_lock_click} />
+                <span>{name}</span>
+            </p>
+            <SlotErrors errors={hunter.get_slot_errors(category, slot)} />
+        </div>
+    }
+}
+
+#[function_component]
+pub fn SquadHunter(props: &SquadHunterProps) -> Html {
+    let SquadHunterProps {
+        id,
+        hunter,
+        budget,
+        on_toggle_lock,
+    } = props;
+
+    let lock_click = |category: TransactionCategory, slot: u8| {
+        let on_toggle_lock = on_toggle_lock.clone();
+        let id = *id;
+
+        Callback::from(move |_| on_toggle_lock.emit((id, category, slot)))
+    };
+
+    let weapons_html = [&hunter.weapon_one, &hunter.weapon_two]
+        .into_iter()
+        .zip(0..)
+        .map(|(weapon, slot)| {
+            slot_html(
+                hunter,
+                weapon,
+                TransactionCategory::Weapon,
+                slot,
+                lock_click(TransactionCategory::Weapon, slot),
+            )
+        })
+        .collect::<Html>();
+
+    let tools_html = hunter
+        .tools
+        .iter()
+        .zip(0..)
+        .map(|(tool, slot)| {
+            slot_html(
+                hunter,
+                tool,
+                TransactionCategory::Tool,
+                slot,
+                lock_click(TransactionCategory::Tool, slot),
+            )
+        })
+        .collect::<Html>();
+
+    let consumables_html = hunter
+        .consumables
+        .iter()
+        .zip(0..)
+        .map(|(consumable, slot)| {
+            slot_html(
+                hunter,
+                consumable,
+                TransactionCategory::Consumable,
+                slot,
+                lock_click(TransactionCategory::Consumable, slot),
+            )
+        })
+        .collect::<Html>();
+
+    let errors_html = hunter
+        .errors
+        .iter()
+        .filter_map(|error| match error {
+            LoadoutError::Budget { error } | LoadoutError::Rule { error } => Some(html! {
+                <p class={classes!("has-text-danger")}>{error}</p>
+            }),
+            _ => None,
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class={classes!("column", "squad-hunter")}>
+            <p class={classes!("subtitle")}>{format!("Hunter {}", id + 1)}</p>
+            {errors_html}
+            <p class={classes!("has-text-weight-bold")}>{"Weapons"}</p>
+            {weapons_html}
+            <p class={classes!("has-text-weight-bold")}>{"Tools"}</p>
+            {tools_html}
+            <p class={classes!("has-text-weight-bold")}>{"Consumables"}</p>
+            {consumables_html}
+            <p>{format!("Total Cost: {}", budget.total_cost)}</p>
+        </div>
+    }
+}
@@ -1,6 +1,8 @@
 use yew::prelude::*;
 
+use crate::components::SlotErrors;
 use crate::content::GenericItem;
+use crate::randomizer::loadout::LoadoutError;
 use crate::TRANSPARENT_B64;
 
 #[derive(PartialEq, Properties)]
@@ -8,6 +10,8 @@ pub struct ToolSlotProps {
     pub tool: Option<GenericItem>,
     pub locked: bool,
     pub id: usize,
+    // Failed purchases for this slot.
+    pub errors: Vec<LoadoutError>,
     pub on_tool_slot_clicked: Callback<usize>,
     pub on_tool_toggle_lock: Callback<usize>,
     pub on_tool_delete: Callback<usize>,
@@ -20,6 +24,7 @@ pub fn ToolSlot(props: &ToolSlotProps) -> Html {
         tool,
         locked,
         id,
+        errors,
         on_tool_slot_clicked,
         on_tool_toggle_lock,
         on_tool_delete,
@@ -83,6 +88,7 @@ pub fn ToolSlot(props: &ToolSlotProps) -> Html {
                     onclick={on_tool_slot_clicked_handle}
                 />
                 <span class={classes!("tool-name")}>{tool.name}</span>
+                <SlotErrors errors={errors.clone()} />
             </div>
         }
     } else {
@@ -99,6 +105,7 @@ pub fn ToolSlot(props: &ToolSlotProps) -> Html {
                     alt={""}
                     onclick={on_tool_slot_clicked_handle}
                 />
+                <SlotErrors errors={errors.clone()} />
             </div>
         }
     }
@@ -0,0 +1,247 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::prelude::*;
+
+use crate::content::{GenericItem, CORE_SEARCH_UTIL};
+use crate::randomizer::{unlocks::UnlockKey, Unlocks};
+
+#[derive(PartialEq, Properties)]
+pub struct UnlockCheckboxProps {
+    pub name: String,
+    pub unlock_key: UnlockKey,
+    pub unlocks: Unlocks,
+    pub max_rank: u8,
+    pub on_unlock_toggle: Callback<UnlockKey>,
+}
+
+#[function_component]
+pub fn UnlockCheckbox(props: &UnlockCheckboxProps) -> Html {
+    let UnlockCheckboxProps {
+        name,
+        unlock_key,
+        unlocks,
+        max_rank,
+        on_unlock_toggle,
+    } = props;
+
+    let unlocked = unlocks.is_unlocked(unlock_key, *max_rank);
+    // Items unlocked through rank or met requirements can't be unchecked, only items the player
+    // marked themselves can.
+    let derived = unlocked && !unlocks.is_marked(unlock_key);
+
+    let on_unlock_change = {
+        let on_unlock_toggle = on_unlock_toggle.clone();
+        let unlock_key = unlock_key.clone();
+
+        move |_| {
+            on_unlock_toggle.emit(unlock_key.clone());
+        }
+    };
+
+    html! {
+        <label class={classes!("checkbox")}>
+            <input
+                type="checkbox"
+                checked={unlocked}
+                disabled={derived}
+                onchange={on_unlock_change}
+            />
+            {name}
+        </label>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct UnlockTrackerProps {
+    pub is_active: bool,
+    pub unlocks: Unlocks,
+    pub max_rank: u8,
+    pub on_unlocks_close: Callback<Unlocks>,
+}
+
+#[function_component]
+pub fn UnlockTracker(props: &UnlockTrackerProps) -> Html {
+    let UnlockTrackerProps {
+        is_active,
+        unlocks,
+        max_rank,
+        on_unlocks_close,
+    } = props;
+
+    let max_rank = *max_rank;
+
+    let unlocks_handle = use_state(|| unlocks.clone());
+    let unlocks = (*unlocks_handle).clone();
+
+    let search_terms_handle = use_state(String::new);
+    let search_terms = (*search_terms_handle).to_lowercase();
+
+    let on_unlocks_close_click = {
+        let on_unlocks_close = on_unlocks_close.clone();
+        let unlocks = unlocks.clone();
+
+        move |_| {
+            on_unlocks_close.emit(unlocks.clone());
+        }
+    };
+
+    let on_search_field_input = move |e: InputEvent| {
+        let target: Option<EventTarget> = e.target();
+
+        let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+        if let Some(input) = input {
+            search_terms_handle.set(input.value());
+        }
+    };
+
+    let on_unlock_toggle = {
+        let unlocks_handle = unlocks_handle.clone();
+        let unlocks = unlocks.clone();
+
+        Callback::from(move |unlock_key: UnlockKey| {
+            let mut unlocks = unlocks.clone();
+
+            if unlocks.is_marked(&unlock_key) {
+                unlocks.lock(&unlock_key);
+            } else {
+                unlocks.unlock(unlock_key);
+            }
+
+            unlocks_handle.set(unlocks);
+        })
+    };
+
+    let requirements_html = CORE_SEARCH_UTIL
+        .get_progress_requirements()
+        .into_iter()
+        .map(|requirement| {
+            let met = unlocks.requirements.contains(requirement);
+
+            let on_requirement_change = {
+                let unlocks_handle = unlocks_handle.clone();
+                let unlocks = unlocks.clone();
+                let requirement = requirement.clone();
+
+                move |_| {
+                    let mut unlocks = unlocks.clone();
+
+                    if met {
+                        unlocks.unmeet_requirement(&requirement);
+                    } else {
+                        unlocks.meet_requirement(requirement.clone());
+                    }
+
+                    unlocks_handle.set(unlocks);
+                }
+            };
+
+            html! {
+                <label class={classes!("checkbox")}>
+                    <input type="checkbox" checked={met} onchange={on_requirement_change} />
+                    {requirement.to_string()}
+                </label>
+            }
+        })
+        .collect::<Html>();
+
+    let items_html = |items: &[GenericItem]| {
+        items
+            .iter()
+            .filter(|item| item.to_full_name().to_lowercase().contains(&search_terms))
+            .map(|item| {
+                // Ammo is shared between variants so it's only listed under the base weapon.
+                let ammo_html = if item.variant.is_none() {
+                    item.get_bullets()
+                        .into_iter()
+                        .filter_map(|bullet| bullet.name.as_ref())
+                        .map(|ammo| {
+                            html! {
+                                <UnlockCheckbox
+                                    name={format!("{ammo} Ammo")}
+                                    unlock_key={UnlockKey::Ammo {
+                                        weapon: item.name.clone(),
+                                        ammo: ammo.clone(),
+                                    }}
+                                    unlocks={unlocks.clone()}
+                                    {max_rank}
+                                    on_unlock_toggle={on_unlock_toggle.clone()}
+                                />
+                            }
+                        })
+                        .collect::<Html>()
+                } else {
+                    html! {}
+                };
+
+                html! {
+                    <div class={classes!("unlock-item")}>
+                        <UnlockCheckbox
+                            name={item.to_full_name()}
+                            unlock_key={UnlockKey::from_item(item)}
+                            unlocks={unlocks.clone()}
+                            {max_rank}
+                            on_unlock_toggle={on_unlock_toggle.clone()}
+                        />
+
+                        <div class={classes!("unlock-ammo")}>
+                            {ammo_html}
+                        </div>
+                    </div>
+                }
+            })
+            .collect::<Html>()
+    };
+
+    let is_active = if *is_active { Some("is-active") } else { None };
+
+    html! {
+        <div class={classes!("modal", is_active)}>
+            <div class={classes!("modal-background")} onclick={on_unlocks_close_click.clone()}></div>
+
+            <div class={classes!("modal-content", "options-container")}>
+                <p class={classes!("subtitle", "has-text-centered")}>{"Requirements Met"}</p>
+
+                <div class={classes!("unlocks-list")}>
+                    {requirements_html}
+                </div>
+
+                <p class={classes!("subtitle", "has-text-centered")}>{"Unlocked Items"}</p>
+
+                <input
+                    class={classes!("input")}
+                    type="text"
+                    placeholder="Search"
+                    oninput={on_search_field_input}
+                />
+
+                <div class={classes!("columns")}>
+                    <div class={classes!("column", "unlocks-list")}>
+                        {items_html(&CORE_SEARCH_UTIL.weapons)}
+                    </div>
+                    <div class={classes!("column", "unlocks-list")}>
+                        {items_html(&CORE_SEARCH_UTIL.tools)}
+                    </div>
+                    <div class={classes!("column", "unlocks-list")}>
+                        {items_html(&CORE_SEARCH_UTIL.consumables)}
+                    </div>
+                </div>
+
+                <div class={classes!("columns", "is-centered")}>
+                    <div class={classes!("column", "is-flex-grow-0")}>
+                        <button
+                            class={classes!("button", "is-primary")}
+                            onclick={on_unlocks_close_click.clone()}
+                        >{"Confirm"}</button>
+                    </div>
+                </div>
+            </div>
+
+            <button
+                class={classes!("modal-close", "is-large")}
+                aria-label="close"
+                onclick={on_unlocks_close_click}
+            ></button>
+        </div>
+    }
+}
@@ -1,6 +1,8 @@
 use yew::prelude::*;
 
+use crate::components::SlotErrors;
 use crate::content::{generic_item::CustomAmmo, GenericItem};
+use crate::randomizer::loadout::LoadoutError;
 use crate::TRANSPARENT_B64;
 
 #[derive(PartialEq, Properties)]
@@ -9,6 +11,8 @@ pub struct WeaponSlotProps {
     pub dual_wield: bool,
     pub locked: bool,
     pub ammo_types: Vec<CustomAmmo>,
+    // Failed purchases for this weapon and its ammo.
+    pub errors: Vec<LoadoutError>,
     pub on_weapon_slot_clicked: Callback<MouseEvent>,
     pub on_weapon_toggle_lock: Callback<MouseEvent>,
     pub on_weapon_delete: Callback<MouseEvent>,
@@ -23,6 +27,7 @@ pub fn WeaponSlot(props: &WeaponSlotProps) -> Html {
         dual_wield,
         locked,
         ammo_types,
+        errors,
         on_weapon_slot_clicked,
         on_weapon_toggle_lock,
         on_weapon_delete,
@@ -46,6 +51,7 @@ pub fn WeaponSlot(props: &WeaponSlotProps) -> Html {
                 </div>
             </div>
             <img class={classes!("weapon-img")} src={TRANSPARENT_B64} alt={""} onclick={on_weapon_slot_clicked} />
+            <SlotErrors errors={errors.clone()} />
         </div>
     }, |weapon| {
         let weapon = weapon.clone();
@@ -110,6 +116,7 @@ pub fn WeaponSlot(props: &WeaponSlotProps) -> Html {
                     onclick={on_weapon_slot_clicked}
                 />
                 <span class={classes!("weapon-name")}>{weapon.to_full_name()}</span>
+                <SlotErrors errors={errors.clone()} />
             </div>
         }
     })
@@ -1,9 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use crate::content::GenericItem;
 
-#[derive(Debug, Clone, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum BulletSize {
     Compact,
@@ -34,6 +34,7 @@ impl fmt::Display for BulletSize {
 }
 
 impl BulletSize {
+    #[must_use]
     pub fn to_svg_path(&self, weapon: Option<&GenericItem>) -> String {
         weapon.map_or_else(
             || format!("/images/bullets/{self}.svg").replace(' ', ""),
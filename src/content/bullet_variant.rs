@@ -1,9 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use crate::content::{BulletSize, GenericItem};
 
-#[derive(Debug, Clone, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum BulletVariant {
     ChaosBolt,
@@ -62,6 +62,7 @@ impl fmt::Display for BulletVariant {
 }
 
 impl BulletVariant {
+    #[must_use]
     pub fn to_svg_path(&self, weapon: Option<&GenericItem>, size: &BulletSize) -> String {
         weapon.map_or_else(
             || {
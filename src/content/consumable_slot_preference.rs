@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::content::UtilityType;
+
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Deserialize, Serialize)]
+pub enum ConsumableSlotPreference {
+    NoPreference,
+    Healing,
+    Antidote,
+    Stamina,
+    Explosives,
+    Fire,
+    Poison,
+    // Choke bombs are tools, nothing in the consumables data is tagged choke yet.
+    Choke,
+}
+
+impl ConsumableSlotPreference {
+    // The tag a consumable has to be used or thrown with to fit the preference.
+    #[must_use]
+    pub const fn utility_type(self) -> Option<UtilityType> {
+        match self {
+            Self::NoPreference => None,
+            Self::Healing => Some(UtilityType::Heal),
+            Self::Antidote => Some(UtilityType::Antidote),
+            Self::Stamina => Some(UtilityType::Stamina),
+            Self::Explosives => Some(UtilityType::Explosion),
+            Self::Fire => Some(UtilityType::Fire),
+            Self::Poison => Some(UtilityType::Poison),
+            Self::Choke => Some(UtilityType::Choke),
+        }
+    }
+}
+
+impl fmt::Display for ConsumableSlotPreference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::NoPreference => "NoPreference",
+                Self::Healing => "Healing",
+                Self::Antidote => "Antidote",
+                Self::Stamina => "Stamina",
+                Self::Explosives => "Explosives",
+                Self::Fire => "Fire",
+                Self::Poison => "Poison",
+                Self::Choke => "Choke",
+            }
+        )
+    }
+}
+
+impl TryFrom<String> for ConsumableSlotPreference {
+    type Error = &'static str;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match &*val {
+            "NoPreference" => Ok(Self::NoPreference),
+            "Healing" => Ok(Self::Healing),
+            "Antidote" => Ok(Self::Antidote),
+            "Stamina" => Ok(Self::Stamina),
+            "Explosives" => Ok(Self::Explosives),
+            "Fire" => Ok(Self::Fire),
+            "Poison" => Ok(Self::Poison),
+            "Choke" => Ok(Self::Choke),
+            _ => Err("Invalid consumable slot preference"),
+        }
+    }
+}
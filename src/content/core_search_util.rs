@@ -1,4 +1,8 @@
-use crate::content::{GenericItem, Slot, UsageType};
+use crate::content::{
+    Bullet, BulletSize, BulletVariant, ConsumableSlotPreference, GenericItem, PreviousRequirement,
+    Requirement, Slot, ToolSlotPreference, UsageType, UtilityType, WeaponSlotPreference,
+    WeaponVariant,
+};
 
 // This was a bit bit of an oversight, but finding decoys using conventional usage_type searching
 // is not viable since decoys are typically "throwable", "melee" and cause damage... Which is the
@@ -13,6 +17,22 @@ const VALID_OTHER_TOOLS: &[&str] = &[
     "Flare Pistol",
     "Quad Derringer",
 ];
+// Bows and crossbows share special ammo with other weapons, so they're found by name as well.
+const VALID_BOW_NAMES: &[&str] = &["Hunting Bow", "Crossbow", "Hand Crossbow"];
+
+// Unlock chains are only a handful of items long, this only stops a bad reference in the json files
+// from recursing forever. Shared by everything that follows previous requirements.
+pub const MAX_REQUIREMENT_DEPTH: u8 = 16;
+
+// What a `PreviousRequirement` points to in the catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequirementTarget<'a> {
+    Item(&'a GenericItem),
+    Ammo {
+        weapon: &'a GenericItem,
+        bullet: &'a Bullet,
+    },
+}
 
 pub struct CoreSearchUtil {
     pub tools: Vec<GenericItem>,
@@ -21,6 +41,7 @@ pub struct CoreSearchUtil {
 }
 
 impl CoreSearchUtil {
+    #[must_use]
     pub fn get_weapons_by_sizes(&self, sizes: &[Slot]) -> Vec<&GenericItem> {
         self.weapons
             .iter()
@@ -33,6 +54,74 @@ impl CoreSearchUtil {
             .collect::<Vec<&GenericItem>>()
     }
 
+    #[must_use]
+    pub fn get_weapons_by_bullet_size(&self, bullet_size: &BulletSize) -> Vec<&GenericItem> {
+        self.weapons
+            .iter()
+            .filter(|weapon| weapon.get_bullet_size().as_ref() == Some(bullet_size))
+            .collect::<Vec<&GenericItem>>()
+    }
+
+    #[must_use]
+    pub fn get_bows(&self) -> Vec<&GenericItem> {
+        self.weapons
+            .iter()
+            .filter(|weapon| VALID_BOW_NAMES.contains(&weapon.name.as_str()))
+            .collect::<Vec<&GenericItem>>()
+    }
+
+    #[must_use]
+    pub fn get_pistols(&self) -> Vec<&GenericItem> {
+        self.weapons
+            .iter()
+            .filter(|weapon| {
+                weapon.slot == Some(Slot::Small)
+                    && weapon.get_bullet_size().is_some()
+                    && !VALID_BOW_NAMES.contains(&weapon.name.as_str())
+            })
+            .collect::<Vec<&GenericItem>>()
+    }
+
+    #[must_use]
+    pub fn get_melee_weapons(&self) -> Vec<&GenericItem> {
+        self.weapons
+            .iter()
+            .filter(|weapon| weapon.get_bullet_size().is_none())
+            .collect::<Vec<&GenericItem>>()
+    }
+
+    // Counts custom ammo, so most of these also need explosive ammo equipped.
+    #[must_use]
+    pub fn get_explosive_weapons(&self) -> Vec<&GenericItem> {
+        self.weapons
+            .iter()
+            .filter(|weapon| {
+                weapon.get_bullets().into_iter().any(|bullet| {
+                    bullet
+                        .types
+                        .as_ref()
+                        .is_some_and(|types| types.contains(&UtilityType::Explosion))
+                })
+            })
+            .collect::<Vec<&GenericItem>>()
+    }
+
+    #[must_use]
+    pub fn get_weapons_by_preference(&self, preference: WeaponSlotPreference) -> Vec<&GenericItem> {
+        match preference {
+            WeaponSlotPreference::NoPreference => {
+                self.weapons.iter().collect::<Vec<&GenericItem>>()
+            }
+            WeaponSlotPreference::Shotgun => self.get_weapons_by_bullet_size(&BulletSize::Shell),
+            WeaponSlotPreference::LongRifle => self.get_weapons_by_bullet_size(&BulletSize::Long),
+            WeaponSlotPreference::Pistol => self.get_pistols(),
+            WeaponSlotPreference::Bow => self.get_bows(),
+            WeaponSlotPreference::Melee => self.get_melee_weapons(),
+            WeaponSlotPreference::Explosive => self.get_explosive_weapons(),
+        }
+    }
+
+    #[must_use]
     pub fn get_decoy_tools(&self) -> Vec<&GenericItem> {
         self.tools
             .iter()
@@ -40,6 +129,7 @@ impl CoreSearchUtil {
             .collect::<Vec<&GenericItem>>()
     }
 
+    #[must_use]
     pub fn get_trip_mines(&self) -> Vec<&GenericItem> {
         self.tools
             .iter()
@@ -51,6 +141,7 @@ impl CoreSearchUtil {
             .collect::<Vec<&GenericItem>>()
     }
 
+    #[must_use]
     pub fn get_melee_tools(&self) -> Vec<&GenericItem> {
         self.tools
             .iter()
@@ -65,6 +156,7 @@ impl CoreSearchUtil {
             .collect::<Vec<&GenericItem>>()
     }
 
+    #[must_use]
     pub fn get_throwables(&self) -> Vec<&GenericItem> {
         self.tools
             .iter()
@@ -77,6 +169,7 @@ impl CoreSearchUtil {
             .collect::<Vec<&GenericItem>>()
     }
 
+    #[must_use]
     pub fn get_other_tools(&self) -> Vec<&GenericItem> {
         self.tools
             .iter()
@@ -84,20 +177,253 @@ impl CoreSearchUtil {
             .collect::<Vec<&GenericItem>>()
     }
 
+    #[must_use]
+    pub fn get_tools_by_preference(&self, preference: ToolSlotPreference) -> Vec<&GenericItem> {
+        match preference {
+            ToolSlotPreference::NoPreference => self.tools.iter().collect::<Vec<&GenericItem>>(),
+            ToolSlotPreference::Decoys => self.get_decoy_tools(),
+            ToolSlotPreference::Tripmines => self.get_trip_mines(),
+            ToolSlotPreference::Melee => self.get_melee_tools(),
+            ToolSlotPreference::Throwable => self.get_throwables(),
+            ToolSlotPreference::Medkit => self
+                .tools
+                .iter()
+                .filter(|tool| tool.to_full_name().as_str() == "First Aid Kit")
+                .collect::<Vec<&GenericItem>>(),
+            ToolSlotPreference::Others => self.get_other_tools(),
+        }
+    }
+
+    // Consumables used or thrown with the tag, hitting someone with a syringe doesn't count.
+    #[must_use]
+    pub fn get_consumables_by_utility_type(&self, utility_type: &UtilityType) -> Vec<&GenericItem> {
+        self.consumables
+            .iter()
+            .filter(|consumable| {
+                consumable
+                    .usage_types
+                    .iter()
+                    .any(|usage_type| match usage_type {
+                        UsageType::Use { types, .. } | UsageType::Throw { types, .. } => {
+                            types.contains(utility_type)
+                        }
+                        _ => false,
+                    })
+            })
+            .collect::<Vec<&GenericItem>>()
+    }
+
+    #[must_use]
+    pub fn get_consumables_by_preference(
+        &self,
+        preference: ConsumableSlotPreference,
+    ) -> Vec<&GenericItem> {
+        preference.utility_type().map_or_else(
+            || self.consumables.iter().collect::<Vec<&GenericItem>>(),
+            |utility_type| self.get_consumables_by_utility_type(&utility_type),
+        )
+    }
+
+    #[must_use]
     pub fn name_is_tool(&self, name: &str) -> bool {
         self.tools.iter().any(|tool| tool.name == name)
     }
 
+    #[must_use]
     pub fn name_is_consumable(&self, name: &str) -> bool {
         self.consumables
             .iter()
             .any(|consumable| consumable.name == name)
     }
 
+    #[must_use]
     pub fn get_dual_wield_weapons(&self) -> Vec<&GenericItem> {
         self.weapons
             .iter()
             .filter(|weapon| weapon.can_dual_wield())
             .collect::<Vec<&GenericItem>>()
     }
+
+    #[must_use]
+    pub fn get_weapon(&self, name: &str, variant: Option<&WeaponVariant>) -> Option<&GenericItem> {
+        self.weapons
+            .iter()
+            .find(|weapon| weapon.name == name && weapon.variant.as_ref() == variant)
+    }
+
+    #[must_use]
+    pub fn get_tool(&self, name: &str) -> Option<&GenericItem> {
+        self.tools.iter().find(|tool| tool.name == name)
+    }
+
+    #[must_use]
+    pub fn get_consumable(&self, name: &str) -> Option<&GenericItem> {
+        self.consumables
+            .iter()
+            .find(|consumable| consumable.name == name)
+    }
+
+    // Every ammo variant any weapon can use, listed once.
+    pub fn get_ammo_variants(&self) -> Vec<&BulletVariant> {
+        let mut variants: Vec<&BulletVariant> = vec![];
+
+        for variant in self
+            .weapons
+            .iter()
+            .flat_map(GenericItem::get_bullets)
+            .filter_map(|bullet| bullet.name.as_ref())
+        {
+            if !variants.contains(&variant) {
+                variants.push(variant);
+            }
+        }
+
+        variants
+    }
+
+    // Every experience, extract and use requirement in the data, these are the requirements a
+    // player can mark as met.
+    #[must_use]
+    pub fn get_progress_requirements(&self) -> Vec<&Requirement> {
+        let mut requirements: Vec<&Requirement> = vec![];
+
+        let all_requirements = self
+            .weapons
+            .iter()
+            .chain(&self.tools)
+            .chain(&self.consumables)
+            .flat_map(|item| {
+                item.requirements.iter().chain(
+                    item.get_bullets()
+                        .into_iter()
+                        .filter_map(|bullet| bullet.requirements.as_ref())
+                        .flatten(),
+                )
+            });
+
+        for requirement in all_requirements {
+            if matches!(
+                requirement,
+                Requirement::Experience { .. }
+                    | Requirement::Extract { .. }
+                    | Requirement::Use { .. }
+            ) && !requirements.contains(&requirement)
+            {
+                requirements.push(requirement);
+            }
+        }
+
+        requirements
+    }
+
+    // The bloodline rank needed to unlock an item, following previous requirements since
+    // weapon variants only list the item they are unlocked from rather than a rank.
+    #[must_use]
+    pub fn get_item_rank(&self, item: &GenericItem) -> u8 {
+        self.get_requirements_rank(&item.requirements, 0)
+    }
+
+    // The bloodline rank needed to use `variant` ammo on `weapon`, this is never lower than the
+    // rank of the weapon itself.
+    #[must_use]
+    pub fn get_bullet_rank(&self, weapon: &GenericItem, variant: &BulletVariant) -> u8 {
+        let bullet_rank = weapon
+            .get_bullet(variant)
+            .and_then(|bullet| bullet.requirements.as_ref())
+            .map_or(0, |requirements| {
+                self.get_requirements_rank(requirements, 0)
+            });
+
+        bullet_rank.max(self.get_item_rank(weapon))
+    }
+
+    fn get_requirements_rank(&self, requirements: &[Requirement], depth: u8) -> u8 {
+        if depth > MAX_REQUIREMENT_DEPTH {
+            return 0;
+        }
+
+        requirements
+            .iter()
+            .map(|requirement| match requirement {
+                Requirement::Rank(rank) => *rank,
+                Requirement::PreviousRequirements(previous) => {
+                    self.get_previous_requirement_rank(previous, depth + 1)
+                }
+                Requirement::Experience { .. }
+                | Requirement::Extract { .. }
+                | Requirement::Use { .. } => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn get_previous_requirement_rank(&self, previous: &PreviousRequirement, depth: u8) -> u8 {
+        match self.resolve_previous_requirement(previous) {
+            Some(RequirementTarget::Item(item)) => {
+                self.get_requirements_rank(&item.requirements, depth)
+            }
+            Some(RequirementTarget::Ammo { weapon, bullet }) => {
+                let bullet_rank = bullet.requirements.as_ref().map_or(0, |requirements| {
+                    self.get_requirements_rank(requirements, depth)
+                });
+
+                bullet_rank.max(self.get_requirements_rank(&weapon.requirements, depth))
+            }
+            None => 0,
+        }
+    }
+
+    // Maps the names in a `PreviousRequirement` back to the catalog, `None` means the reference is
+    // dangling (usually a typo in the json files).
+    pub fn resolve_previous_requirement(
+        &self,
+        previous: &PreviousRequirement,
+    ) -> Option<RequirementTarget<'_>> {
+        match previous {
+            PreviousRequirement::Weapon { weapon, variant } => self
+                .get_weapon(weapon, variant.as_ref())
+                .map(RequirementTarget::Item),
+            // Ammo is unlocked on the base weapon.
+            PreviousRequirement::Ammo { weapon, ammo } => {
+                self.get_weapon(weapon, None).and_then(|weapon| {
+                    weapon
+                        .get_bullet(ammo)
+                        .map(|bullet| RequirementTarget::Ammo { weapon, bullet })
+                })
+            }
+            PreviousRequirement::Tool { tool } => self.get_tool(tool).map(RequirementTarget::Item),
+            PreviousRequirement::Consumable { consumable } => {
+                self.get_consumable(consumable).map(RequirementTarget::Item)
+            }
+        }
+    }
+
+    // Every previous requirement in the data that doesn't resolve, paired with the full name of
+    // the item it's on.
+    #[must_use]
+    pub fn get_dangling_requirements(&self) -> Vec<(String, &PreviousRequirement)> {
+        self.weapons
+            .iter()
+            .chain(&self.tools)
+            .chain(&self.consumables)
+            .flat_map(|item| {
+                item.requirements
+                    .iter()
+                    .chain(
+                        item.get_bullets()
+                            .into_iter()
+                            .filter_map(|bullet| bullet.requirements.as_ref())
+                            .flatten(),
+                    )
+                    .filter_map(move |requirement| match requirement {
+                        Requirement::PreviousRequirements(previous)
+                            if self.resolve_previous_requirement(previous).is_none() =>
+                        {
+                            Some((item.to_full_name(), previous))
+                        }
+                        _ => None,
+                    })
+            })
+            .collect::<Vec<(String, &PreviousRequirement)>>()
+    }
 }
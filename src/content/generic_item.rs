@@ -1,7 +1,8 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::content::{
-    BulletSize, BulletVariant, Requirement, Slot, UsageType, WeaponVariant, CORE_SEARCH_UTIL,
+    Bullet, BulletSize, BulletVariant, Requirement, Slot, UsageType, WeaponVariant,
+    CORE_SEARCH_UTIL,
 };
 use crate::randomizer::loadout::INVALID_DUALWIELD_NAMES;
 
@@ -17,13 +18,13 @@ const fn default_ammo() -> Vec<CustomAmmo> {
 
 pub type CustomAmmo = (BulletSize, Option<BulletVariant>, u16);
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct GenericItemLockable {
     pub item: Option<GenericItem>,
     pub locked: bool,
 }
 
-#[derive(Clone, Deserialize, Debug, Eq, PartialEq)]
+#[derive(Clone, Deserialize, Serialize, Debug, Eq, PartialEq)]
 pub struct GenericItem {
     pub name: String,
     // Only used for Caldwell Conversion Pistol
@@ -45,6 +46,7 @@ pub struct GenericItem {
 }
 
 impl GenericItem {
+    #[must_use]
     pub const fn get_cost(&self) -> u16 {
         if self.dual_wield {
             self.cost * 2
@@ -53,6 +55,7 @@ impl GenericItem {
         }
     }
 
+    #[must_use]
     pub fn get_slot(&self) -> Slot {
         if self.dual_wield {
             Slot::Medium
@@ -66,6 +69,7 @@ impl GenericItem {
         }
     }
 
+    #[must_use]
     pub fn get_bullet_variants(&self) -> Vec<CustomAmmo> {
         self.usage_types
             .iter()
@@ -100,6 +104,27 @@ impl GenericItem {
             .collect::<Vec<CustomAmmo>>()
     }
 
+    #[must_use]
+    pub fn get_bullets(&self) -> Vec<&Bullet> {
+        self.usage_types
+            .iter()
+            .filter_map(|usage_type| match usage_type {
+                UsageType::Shoot { bullet_types, .. }
+                | UsageType::ShootSecondary { bullet_types, .. } => Some(bullet_types),
+                _ => None,
+            })
+            .flatten()
+            .collect::<Vec<&Bullet>>()
+    }
+
+    #[must_use]
+    pub fn get_bullet(&self, variant: &BulletVariant) -> Option<&Bullet> {
+        self.get_bullets()
+            .into_iter()
+            .find(|bullet| bullet.name.as_ref() == Some(variant))
+    }
+
+    #[must_use]
     pub fn get_bullet_size(&self) -> Option<BulletSize> {
         let mut found_bullet_size = None;
 
@@ -113,6 +138,7 @@ impl GenericItem {
         found_bullet_size
     }
 
+    #[must_use]
     pub fn can_dual_wield(&self) -> bool {
         if let Some(slot) = &self.slot {
             if *slot == Slot::Small && !INVALID_DUALWIELD_NAMES.contains(&self.name.as_str()) {
@@ -136,6 +162,7 @@ impl GenericItem {
         )
     }
 
+    #[must_use]
     pub fn to_image_path(&self) -> String {
         // Just resolves to weapon path until I make a way for it to differentiate
         // weapon/consumable/tool.
@@ -163,10 +190,12 @@ impl GenericItem {
         )
     }
 
+    #[must_use]
     pub fn to_tool_path(&self) -> String {
         format!("/images/tools/{}.webp", self.name.replace([' ', '.'], ""),)
     }
 
+    #[must_use]
     pub fn to_consumable_path(&self) -> String {
         format!(
             "/images/consumables/{}.webp",
@@ -1,21 +1,26 @@
 pub mod bullet;
 pub mod bullet_size;
 pub mod bullet_variant;
+pub mod consumable_slot_preference;
 pub mod core_search_util;
 pub mod generic_item;
 pub mod tool_slot_preference;
+pub mod weapon_slot_preference;
 pub mod weapon_variant;
 
 pub use bullet::Bullet;
 pub use bullet_size::BulletSize;
 pub use bullet_variant::BulletVariant;
+pub use consumable_slot_preference::ConsumableSlotPreference;
 pub use core_search_util::CoreSearchUtil;
 pub use generic_item::GenericItem;
 pub use tool_slot_preference::ToolSlotPreference;
+pub use weapon_slot_preference::WeaponSlotPreference;
 pub use weapon_variant::WeaponVariant;
 
-use serde::Deserialize;
-use serde_repr::Deserialize_repr;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::fmt;
 
 lazy_static! {
     pub static ref CORE_SEARCH_UTIL: CoreSearchUtil = CoreSearchUtil {
@@ -50,13 +55,13 @@ pub enum ItemVariant {
     Tool,
 }
 
-#[derive(Clone, Deserialize, Debug, Eq, PartialEq)]
+#[derive(Clone, Deserialize, Serialize, Debug, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExtractCategory {
     Light,
 }
 
-#[derive(Clone, Deserialize, Debug, Eq, PartialEq)]
+#[derive(Clone, Deserialize, Serialize, Debug, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum UtilityType {
     Antidote,
@@ -75,7 +80,7 @@ pub enum UtilityType {
     Stamina,
 }
 
-#[derive(Clone, Deserialize_repr, Debug, Eq, PartialEq)]
+#[derive(Clone, Deserialize_repr, Serialize_repr, Debug, Eq, PartialEq)]
 #[repr(u8)]
 pub enum Intensity {
     Light = 0,
@@ -83,7 +88,7 @@ pub enum Intensity {
     Heavy,
 }
 
-#[derive(Clone, Deserialize_repr, Debug, Eq, PartialEq)]
+#[derive(Clone, Deserialize_repr, Serialize_repr, Debug, Eq, PartialEq)]
 #[repr(u8)]
 pub enum Slot {
     Small = 0,
@@ -91,7 +96,7 @@ pub enum Slot {
     Large,
 }
 
-#[derive(Clone, Deserialize, Debug, Eq, PartialEq)]
+#[derive(Clone, Deserialize, Serialize, Debug, Eq, PartialEq)]
 #[serde(tag = "req_type", rename_all = "snake_case")]
 pub enum PreviousRequirement {
     Ammo {
@@ -110,7 +115,25 @@ pub enum PreviousRequirement {
     },
 }
 
-#[derive(Clone, Deserialize, Debug, Eq, PartialEq)]
+impl fmt::Display for PreviousRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ammo { weapon, ammo } => write!(f, "{ammo} ammo for the {weapon}"),
+            Self::Consumable { consumable } => write!(f, "{consumable}"),
+            Self::Tool { tool } => write!(f, "{tool}"),
+            Self::Weapon {
+                weapon,
+                variant: Some(variant),
+            } => write!(f, "{weapon} {variant}"),
+            Self::Weapon {
+                weapon,
+                variant: None,
+            } => write!(f, "{weapon}"),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, Eq, PartialEq)]
 #[serde(tag = "t", content = "c", rename_all = "snake_case")]
 pub enum Requirement {
     Rank(u8),
@@ -129,7 +152,23 @@ pub enum Requirement {
     PreviousRequirements(PreviousRequirement),
 }
 
-#[derive(Clone, Deserialize, Debug, Eq, PartialEq)]
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rank(rank) => write!(f, "Reach bloodline rank {rank}"),
+            Self::Experience { amount, variant } => {
+                write!(f, "Earn {amount} experience with the {variant}")
+            }
+            Self::Extract { category, times } => {
+                write!(f, "Extract with a {category:?} item {times} times")
+            }
+            Self::Use { category, times } => write!(f, "Use a {category:?} item {times} times"),
+            Self::PreviousRequirements(previous) => write!(f, "Unlock {previous}"),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, Eq, PartialEq)]
 #[serde(tag = "t", content = "c", rename_all = "snake_case")]
 pub enum UsageType {
     BasicMelee {
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Deserialize, Serialize)]
 pub enum ToolSlotPreference {
     NoPreference,
     Medkit,
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Deserialize, Serialize)]
+pub enum WeaponSlotPreference {
+    NoPreference,
+    Shotgun,
+    LongRifle,
+    Pistol,
+    Bow,
+    Melee,
+    Explosive,
+}
+
+impl fmt::Display for WeaponSlotPreference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::NoPreference => "NoPreference",
+                Self::Shotgun => "Shotgun",
+                Self::LongRifle => "LongRifle",
+                Self::Pistol => "Pistol",
+                Self::Bow => "Bow",
+                Self::Melee => "Melee",
+                Self::Explosive => "Explosive",
+            }
+        )
+    }
+}
+
+impl TryFrom<String> for WeaponSlotPreference {
+    type Error = &'static str;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match &*val {
+            "NoPreference" => Ok(Self::NoPreference),
+            "Shotgun" => Ok(Self::Shotgun),
+            "LongRifle" => Ok(Self::LongRifle),
+            "Pistol" => Ok(Self::Pistol),
+            "Bow" => Ok(Self::Bow),
+            "Melee" => Ok(Self::Melee),
+            "Explosive" => Ok(Self::Explosive),
+            _ => Err("Invalid weapon slot preference"),
+        }
+    }
+}
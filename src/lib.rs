@@ -0,0 +1,18 @@
+//! The item catalog and loadout randomizer, shared by the website and the command line generator in
+//! `src/bin/hunt-cli.rs`. Nothing in here depends on yew or the browser, the website is only built
+//! with the `web` feature.
+
+#![warn(
+    clippy::all,
+    clippy::pedantic,
+    clippy::nursery,
+    clippy::cargo,
+    clippy::style
+)]
+#![allow(clippy::module_name_repetitions)]
+
+#[macro_use]
+extern crate lazy_static;
+
+pub mod content;
+pub mod randomizer;
@@ -7,23 +7,26 @@
 )]
 #![allow(clippy::module_name_repetitions)]
 
-#[macro_use]
-extern crate lazy_static;
-
+use hunt_app::{content, randomizer};
 use yew::prelude::*;
 use yew_router::prelude::*;
 
 mod components;
-mod content;
 mod pages;
-mod randomizer;
+mod storage;
 
 pub const TRANSPARENT_B64: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mNkYAAAAAYAAjCB0C8AAAAASUVORK5CYII=";
 
-#[derive(Routable, Clone, PartialEq, Eq, Copy)]
+#[derive(Routable, Clone, PartialEq, Eq)]
 pub enum Route {
     #[at("/")]
     Home,
+    #[at("/squad")]
+    Squad,
+    #[at("/unlock-planner")]
+    UnlockPlanner,
+    #[at("/loadout/:code")]
+    Loadout { code: String },
     #[not_found]
     #[at("/404")]
     NotFound,
@@ -54,6 +57,15 @@ fn switch(routes: Route) -> Html {
         Route::Home => {
             html! { <pages::RandomLoadout /> }
         }
+        Route::Loadout { code } => {
+            html! { <pages::RandomLoadout code={Some(code)} /> }
+        }
+        Route::Squad => {
+            html! { <pages::SquadLoadouts /> }
+        }
+        Route::UnlockPlanner => {
+            html! { <pages::UnlockPlanner /> }
+        }
         Route::NotFound => {
             html! { <pages::PageNotFound /> }
         }
@@ -1,21 +1,93 @@
+use rand::{rngs::StdRng, SeedableRng};
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
 use yew::prelude::*;
+use yew_router::prelude::*;
 
 use crate::components::{
-    AdvancedOptions, BudgetDisplay, BulletSelectList, ConsumableSlot, ItemSelectList, ToolSlot,
-    WeaponSlot,
+    AdvancedOptions, BudgetDisplay, BulletSelectList, ConsumableSlot, ItemSelectList,
+    LoadoutJsonBox, ToolSlot, UnlockTracker, WeaponSlot,
 };
 use crate::content::{generic_item::CustomAmmo, GenericItem, ItemVariant, CORE_SEARCH_UTIL};
-use crate::randomizer::budget::Transaction;
+use crate::randomizer::budget::{Transaction, TransactionCategory};
 use crate::randomizer::{
-    budget, config::ToggleOption, loadout, Budget, Config, Loadout, LoadoutInvalid,
+    budget,
+    config::ToggleOption,
+    loadout,
+    loadout::LoadoutError,
+    loadout_code, loadout_json,
+    loadout_text::{self, TextFormat},
+    Budget, Config, LoadoutInvalid, Unlocks,
 };
+use crate::storage;
+use crate::Route;
+
+const ERR_INVALID_SEED: &str = "A seed is a whole number from 0 to 18446744073709551615.";
+
+#[derive(PartialEq, Eq, Properties)]
+pub struct RandomLoadoutProps {
+    // Code from a shared loadout link, see `randomizer::loadout_code`.
+    #[prop_or_default]
+    pub code: Option<String>,
+}
+
+fn copy_to_clipboard(text: &str) {
+    let Some(window) = web_sys::window() else {
+        log::error!("Window is not available, unable to copy to the clipboard");
+        return;
+    };
+
+    // The write finishes in the background, there's nothing to do if it fails.
+    let _ = window.navigator().clipboard().write_text(text);
+}
 
 #[function_component]
-pub fn RandomLoadout() -> Html {
-    let budget_handle = use_state(Budget::default);
+pub fn RandomLoadout(props: &RandomLoadoutProps) -> Html {
+    let RandomLoadoutProps { code } = props;
+
+    // Only decoded once, the shared loadout is just where the page starts.
+    let shared_handle = use_state(|| {
+        code.as_deref().map(|code| {
+            loadout_code::decode(code).map(|mut shared| {
+                // Checked against the budget from the last session, the one generating would use.
+                let config = storage::load::<Config>(storage::CONFIG_KEY).unwrap_or_default();
+                let mut budget = Budget::default();
+                loadout::price_loadout(&mut shared.loadout, &mut budget, &config);
+                (shared, budget)
+            })
+        })
+    });
+    let (shared, shared_error) = match &*shared_handle {
+        Some(Ok(shared)) => (Some(shared.clone()), None),
+        Some(Err(error)) => (None, Some(*error)),
+        None => (None, None),
+    };
+
+    // A shared link wins over the last session, which wins over the defaults.
+    let budget_handle = use_state(|| {
+        shared.as_ref().map_or_else(
+            || storage::load(storage::BUDGET_KEY).unwrap_or_default(),
+            |(_, budget)| budget.clone(),
+        )
+    });
     let budget = (*budget_handle).clone();
 
-    let config_handle = use_state(Config::default);
+    let config_handle = use_state(|| {
+        let mut config = Config {
+            unlocks: storage::load(storage::UNLOCKS_KEY).unwrap_or_default(),
+            ..storage::load(storage::CONFIG_KEY).unwrap_or_default()
+        };
+
+        if let Some((shared, _)) = &shared {
+            config.remove_option(ToggleOption::Quartermaster);
+
+            if shared.quartermaster {
+                config.toggled_options.push(ToggleOption::Quartermaster);
+            }
+        }
+
+        config
+    });
     let config = (*config_handle).clone();
 
     let on_dual_wield_change = {
@@ -140,8 +212,64 @@ pub fn RandomLoadout() -> Html {
         }
     };
 
-    let loadout_handle = use_state(Loadout::default);
+    let loadout_handle = use_state(|| {
+        shared.map_or_else(
+            || storage::load(storage::LOADOUT_KEY).unwrap_or_default(),
+            |(shared, _)| shared.loadout,
+        )
+    });
     let loadout = (*loadout_handle).clone();
+
+    // What's typed in the seed input, kept apart from `config.seed` so it can show the seed a
+    // loadout was generated with and text that isn't a seed.
+    let seed_text_handle = use_state(|| {
+        config
+            .seed
+            .or(loadout.seed)
+            .map_or_else(String::new, |seed| seed.to_string())
+    });
+    let seed_text = (*seed_text_handle).clone();
+
+    let seed_error_handle = use_state(|| None::<&'static str>);
+    let seed_error = *seed_error_handle;
+
+    let on_seed_input = {
+        let config = config.clone();
+        let config_handle = config_handle.clone();
+        let seed_text_handle = seed_text_handle.clone();
+        let seed_error_handle = seed_error_handle.clone();
+
+        move |e: InputEvent| {
+            let target: Option<EventTarget> = e.target();
+
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+            if let Some(input) = input {
+                // A text input since seeds use the full `u64` range, number inputs lose precision
+                // past 2^53.
+                let value = input.value();
+                let seed = value.trim();
+
+                // Invalid text keeps the last valid seed until it's fixed.
+                if seed.is_empty() {
+                    let mut config = config.clone();
+                    config.seed = None;
+                    config_handle.set(config);
+                    seed_error_handle.set(None);
+                } else if let Ok(seed) = seed.parse::<u64>() {
+                    let mut config = config.clone();
+                    config.seed = Some(seed);
+                    config_handle.set(config);
+                    seed_error_handle.set(None);
+                } else {
+                    seed_error_handle.set(Some(ERR_INVALID_SEED));
+                }
+
+                seed_text_handle.set(value);
+            }
+        }
+    };
+
     let on_generate_loadout_clicked = {
         let loadout = loadout.clone();
         let config = config.clone();
@@ -154,6 +282,8 @@ pub fn RandomLoadout() -> Html {
         let weapon_two_ammo_slot_handle = weapon_two_ammo_slot_handle.clone();
         let tools_dropdown_handle = tools_dropdown_handle.clone();
         let cons_dropdown_handle = cons_dropdown_handle.clone();
+        let seed_text_handle = seed_text_handle.clone();
+        let seed_error_handle = seed_error_handle.clone();
 
         move |_e: MouseEvent| {
             let mut loadout = loadout.clone();
@@ -166,11 +296,53 @@ pub fn RandomLoadout() -> Html {
             weapon_two_ammo_slot_handle.set(None);
             loadout::random(&mut loadout, &mut budget, &config);
 
+            // Shows the seed that was used, so what's on screen always reproduces the loadout.
+            seed_text_handle.set(
+                loadout
+                    .seed
+                    .map_or_else(String::new, |seed| seed.to_string()),
+            );
+            seed_error_handle.set(None);
             budget_handle.set(budget);
             loadout_handle.set(loadout);
         }
     };
 
+    let import_problems_handle = use_state(Vec::<String>::new);
+    let import_problems = (*import_problems_handle).clone();
+    let on_loadout_import = {
+        let config = config.clone();
+        let loadout_handle = loadout_handle.clone();
+        let budget_handle = budget_handle.clone();
+        let config_handle = config_handle.clone();
+        let weapon_one_ammo_slot_handle = weapon_one_ammo_slot_handle.clone();
+        let weapon_two_ammo_slot_handle = weapon_two_ammo_slot_handle.clone();
+
+        Callback::from(move |json: String| match loadout_json::import(&json) {
+            Ok(mut imported) => {
+                // Costs are worked out again from the item data, not trusted from the json, and
+                // checked against the current budget.
+                let mut budget = Budget::default();
+                loadout::price_loadout(&mut imported.loadout, &mut budget, &config);
+
+                let mut config = config.clone();
+                config.remove_option(ToggleOption::Quartermaster);
+
+                if imported.quartermaster {
+                    config.toggled_options.push(ToggleOption::Quartermaster);
+                }
+
+                weapon_one_ammo_slot_handle.set(None);
+                weapon_two_ammo_slot_handle.set(None);
+                import_problems_handle.set(imported.problems);
+                config_handle.set(config);
+                budget_handle.set(budget);
+                loadout_handle.set(imported.loadout);
+            }
+            Err(error) => import_problems_handle.set(vec![error]),
+        })
+    };
+
     let on_weapon_one_selected = {
         let loadout_handle = loadout_handle.clone();
         let budget_handle = budget_handle.clone();
@@ -181,11 +353,23 @@ pub fn RandomLoadout() -> Html {
         move |(item, _): (Option<GenericItem>, usize)| {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
-            loadout::refund_item(&mut budget, &loadout.weapon_one);
+            loadout::refund_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Weapon,
+                0,
+                &loadout.weapon_one,
+            );
             loadout.weapon_one.item = item;
             loadout.weapon_one.locked = true;
             let _ = loadout::set_default_ammo(&mut loadout.weapon_one);
-            loadout::purchase_item(&mut budget, &loadout.weapon_one);
+            loadout::purchase_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Weapon,
+                0,
+                &loadout.weapon_one,
+            );
             weapon_one_dropdown_handle.set(false);
             budget_handle.set(budget);
             loadout_handle.set(loadout);
@@ -202,11 +386,23 @@ pub fn RandomLoadout() -> Html {
         move |(item, _): (Option<GenericItem>, usize)| {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
-            loadout::refund_item(&mut budget, &loadout.weapon_two);
+            loadout::refund_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Weapon,
+                1,
+                &loadout.weapon_two,
+            );
             loadout.weapon_two.item = item;
             loadout.weapon_two.locked = true;
             let _ = loadout::set_default_ammo(&mut loadout.weapon_two);
-            loadout::purchase_item(&mut budget, &loadout.weapon_two);
+            loadout::purchase_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Weapon,
+                1,
+                &loadout.weapon_two,
+            );
             weapon_two_dropdown_handle.set(false);
             budget_handle.set(budget);
             loadout_handle.set(loadout);
@@ -244,7 +440,13 @@ pub fn RandomLoadout() -> Html {
         move |_: MouseEvent| {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
-            loadout::refund_item(&mut budget, &loadout.weapon_one);
+            loadout::refund_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Weapon,
+                0,
+                &loadout.weapon_one,
+            );
             loadout.weapon_one.item = None;
             loadout.weapon_one.locked = false;
             budget_handle.set(budget);
@@ -261,7 +463,13 @@ pub fn RandomLoadout() -> Html {
         move |_: MouseEvent| {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
-            loadout::refund_item(&mut budget, &loadout.weapon_two);
+            loadout::refund_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Weapon,
+                1,
+                &loadout.weapon_two,
+            );
             loadout.weapon_two.item = None;
             loadout.weapon_two.locked = false;
             budget_handle.set(budget);
@@ -280,7 +488,12 @@ pub fn RandomLoadout() -> Html {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
             loadout.weapon_one.locked = false;
-            loadout::random_weapon_one(&mut loadout, &mut budget, &config);
+            loadout::random_weapon_one(
+                &mut loadout,
+                &mut budget,
+                &config,
+                &mut StdRng::from_entropy(),
+            );
             loadout_handle.set(loadout);
             budget_handle.set(budget);
         }
@@ -297,7 +510,12 @@ pub fn RandomLoadout() -> Html {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
             loadout.weapon_two.locked = false;
-            loadout::random_weapon_two(&mut loadout, &mut budget, &config);
+            loadout::random_weapon_two(
+                &mut loadout,
+                &mut budget,
+                &config,
+                &mut StdRng::from_entropy(),
+            );
             loadout_handle.set(loadout);
             budget_handle.set(budget);
         }
@@ -371,23 +589,25 @@ pub fn RandomLoadout() -> Html {
                         let mut budget = budget.clone();
                         let current_bullet = current_bullet.clone();
 
+                        loadout.errors.retain(|error| !matches!(error, LoadoutError::Ammo { slot: 0, ammo_slot, .. } if usize::from(*ammo_slot) == pos));
+
                         if current_bullet.2 > 0 {
-                            let _ = budget::process_transaction(&mut budget, Transaction::Bullet(true, current_bullet.2, match current_bullet.1 {
+                            let _ = budget::process_transaction(&mut budget, Transaction::refund(TransactionCategory::Bullet, 0, current_bullet.2, match current_bullet.1 {
                                 Some(variant) => variant.to_string(),
                                 None => current_bullet.0.to_string(),
                             })).ok();
                         }
 
                         if let Some(variant) = &bullet.1 {
-                            let tx_res = budget::process_transaction(&mut budget, Transaction::Bullet(false, bullet.2, variant.to_string()));
+                            let tx_res = budget::process_transaction(&mut budget, Transaction::purchase(TransactionCategory::Bullet, 0, bullet.2, variant.to_string()));
                             loadout.weapon_one.locked = true;
 
                             if let Some(weapon_one) = &mut loadout.weapon_one.item {
                                 weapon_one.ammo_equipped[pos] = bullet;
                             }
 
-                            if let Ok(_tx_res) = tx_res {
-                                // Process bullet.
+                            if let Err(e) = tx_res {
+                                loadout.errors.push(LoadoutError::from_transaction(loadout::ERR_INSF_FND_LOCK, TransactionCategory::Bullet, 0, pos.try_into().unwrap_or(0), e));
                             }
                         } else if let Some(weapon_one) = &mut loadout.weapon_one.item {
                             weapon_one.ammo_equipped[pos] = bullet;
@@ -440,8 +660,10 @@ pub fn RandomLoadout() -> Html {
                         let mut budget = budget.clone();
                         let current_bullet = current_bullet.clone();
 
+                        loadout.errors.retain(|error| !matches!(error, LoadoutError::Ammo { slot: 1, ammo_slot, .. } if usize::from(*ammo_slot) == pos));
+
                         if current_bullet.2 > 0 {
-                            let _ = budget::process_transaction(&mut budget, Transaction::Bullet(true, if additional_ammo {
+                            let _ = budget::process_transaction(&mut budget, Transaction::refund(TransactionCategory::Bullet, 1, if additional_ammo {
                                 // As of 1.10 ammo cost is calculated this way.
                                 current_bullet.2 / 2
                             } else { current_bullet.2 }, match current_bullet.1 {
@@ -451,7 +673,7 @@ pub fn RandomLoadout() -> Html {
                         }
 
                         if let Some(variant) = &bullet.1 {
-                            let tx_res = budget::process_transaction(&mut budget, Transaction::Bullet(false, if additional_ammo {
+                            let tx_res = budget::process_transaction(&mut budget, Transaction::purchase(TransactionCategory::Bullet, 1, if additional_ammo {
                                 // As of 1.10 ammo cost is calculated this way.
                                 bullet.2 / 2
                             } else { bullet.2 }, variant.to_string()));
@@ -461,8 +683,8 @@ pub fn RandomLoadout() -> Html {
                                 weapon_two.ammo_equipped[pos] = bullet;
                             }
 
-                            if let Ok(_tx_res) = tx_res {
-                                // Process bullet.
+                            if let Err(e) = tx_res {
+                                loadout.errors.push(LoadoutError::from_transaction(loadout::ERR_INSF_FND_LOCK, TransactionCategory::Bullet, 1, pos.try_into().unwrap_or(0), e));
                             }
                         } else if let Some(weapon_two) = &mut loadout.weapon_two.item {
                             weapon_two.ammo_equipped[pos] = bullet;
@@ -492,9 +714,37 @@ pub fn RandomLoadout() -> Html {
         }
     };
 
-    let on_advanced_options_close = move |new_config: Config| {
-        advanced_options_toggled_handle.set(!advanced_options_toggled);
-        config_handle.set(new_config);
+    let on_advanced_options_close = {
+        let config_handle = config_handle.clone();
+
+        move |new_config: Config| {
+            advanced_options_toggled_handle.set(!advanced_options_toggled);
+            config_handle.set(new_config);
+        }
+    };
+
+    let unlock_tracker_toggled_handle = use_state(|| false);
+    let unlock_tracker_toggled = *unlock_tracker_toggled_handle;
+    let on_unlock_tracker_toggled = {
+        let unlock_tracker_toggled_handle = unlock_tracker_toggled_handle.clone();
+
+        move |_: MouseEvent| {
+            unlock_tracker_toggled_handle.set(!unlock_tracker_toggled);
+        }
+    };
+
+    let on_unlock_tracker_close = {
+        let config = config.clone();
+
+        move |unlocks: Unlocks| {
+            let mut config = config.clone();
+
+            storage::save(storage::UNLOCKS_KEY, &unlocks);
+            config.unlocks = unlocks;
+
+            unlock_tracker_toggled_handle.set(!unlock_tracker_toggled);
+            config_handle.set(config);
+        }
     };
 
     let on_tool_selected = {
@@ -507,9 +757,21 @@ pub fn RandomLoadout() -> Html {
         move |(item, id): (Option<GenericItem>, usize)| {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
-            loadout::refund_item(&mut budget, &loadout.tools[id]);
+            loadout::refund_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Tool,
+                id.try_into().unwrap_or(0),
+                &loadout.tools[id],
+            );
             loadout.tools[id].item = item;
-            loadout::purchase_item(&mut budget, &loadout.tools[id]);
+            loadout::purchase_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Tool,
+                id.try_into().unwrap_or(0),
+                &loadout.tools[id],
+            );
             loadout.tools[id].locked = true;
             tools_dropdown_handle.set([false; 4]);
             loadout_handle.set(loadout);
@@ -572,7 +834,13 @@ pub fn RandomLoadout() -> Html {
         move |id: usize| {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
-            loadout::refund_item(&mut budget, &loadout.tools[id]);
+            loadout::refund_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Tool,
+                id.try_into().unwrap_or(0),
+                &loadout.tools[id],
+            );
             loadout.tools[id].locked = false;
             loadout.tools[id].item = None;
             loadout_handle.set(loadout);
@@ -602,6 +870,7 @@ pub fn RandomLoadout() -> Html {
                 &mut loadout,
                 &mut budget,
                 &config,
+                &mut StdRng::from_entropy(),
                 id.try_into().unwrap_or(0),
             );
             loadout_handle.set(loadout);
@@ -626,9 +895,21 @@ pub fn RandomLoadout() -> Html {
         move |(item, id): (Option<GenericItem>, usize)| {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
-            loadout::refund_item(&mut budget, &loadout.consumables[id]);
+            loadout::refund_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Consumable,
+                id.try_into().unwrap_or(0),
+                &loadout.consumables[id],
+            );
             loadout.consumables[id].item = item;
-            loadout::purchase_item(&mut budget, &loadout.consumables[id]);
+            loadout::purchase_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Consumable,
+                id.try_into().unwrap_or(0),
+                &loadout.consumables[id],
+            );
             loadout.consumables[id].locked = true;
             cons_dropdown_handle.set([false; 4]);
             loadout_handle.set(loadout);
@@ -698,7 +979,13 @@ pub fn RandomLoadout() -> Html {
         move |id: usize| {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
-            loadout::refund_item(&mut budget, &loadout.consumables[id]);
+            loadout::refund_item(
+                &mut budget,
+                &mut loadout.errors,
+                TransactionCategory::Consumable,
+                id.try_into().unwrap_or(0),
+                &loadout.consumables[id],
+            );
             loadout.consumables[id].locked = false;
             loadout.consumables[id].item = None;
             loadout_handle.set(loadout);
@@ -716,12 +1003,19 @@ pub fn RandomLoadout() -> Html {
     let on_consumable_randomize = {
         let loadout = loadout.clone();
         let budget = budget.clone();
+        let config = config.clone();
 
         move |id: usize| {
             let mut loadout = loadout.clone();
             let mut budget = budget.clone();
             loadout.consumables[id].locked = false;
-            loadout::random_consumable(&mut loadout, &mut budget, id.try_into().unwrap_or(0));
+            loadout::random_consumable(
+                &mut loadout,
+                &mut budget,
+                &config,
+                &mut StdRng::from_entropy(),
+                id.try_into().unwrap_or(0),
+            );
             loadout_handle.set(loadout);
             budget_handle.set(budget);
 
@@ -760,6 +1054,39 @@ pub fn RandomLoadout() -> Html {
         }
     }
 
+    // Saved whenever one of them changes so a reload picks up where the last session left off.
+    use_effect_with(
+        (loadout.clone(), budget.clone(), config.clone()),
+        |(loadout, budget, config)| {
+            storage::save(storage::LOADOUT_KEY, loadout);
+            storage::save(storage::BUDGET_KEY, budget);
+            storage::save(storage::CONFIG_KEY, config);
+        },
+    );
+
+    let share_code =
+        loadout_code::encode(&loadout, config.option_exists(ToggleOption::Quartermaster));
+    let on_copy_text_click = |format: TextFormat| {
+        let loadout = loadout.clone();
+        let budget = budget.clone();
+
+        move |_| copy_to_clipboard(&loadout_text::format_loadout(&loadout, &budget, format))
+    };
+
+    let export_json =
+        loadout_json::export(&loadout, config.option_exists(ToggleOption::Quartermaster));
+
+    let loadout_errors_html = loadout
+        .errors
+        .iter()
+        .filter_map(|error| match error {
+            LoadoutError::Budget { error } | LoadoutError::Rule { error } => Some(html! {
+                <p class={classes!("has-text-centered", "has-text-danger")}>{error}</p>
+            }),
+            _ => None,
+        })
+        .collect::<Html>();
+
     html! {
         <>
         <div class={classes!("container", "my-4")}>
@@ -819,6 +1146,15 @@ pub fn RandomLoadout() -> Html {
                     </button>
                 </div>
 
+                <div class={classes!("column", "is-flex-grow-0")}>
+                    <button
+                        class="button"
+                        onclick={on_unlock_tracker_toggled}
+                    >
+                        {"Unlocks"}
+                    </button>
+                </div>
+
                 <div class={classes!("column", "is-flex-grow-0")}>
                     <button
                         class="button"
@@ -827,8 +1163,53 @@ pub fn RandomLoadout() -> Html {
                         {"Generate Loadout"}
                     </button>
                 </div>
+
+                <div class={classes!("column", "is-narrow")}>
+                    <input
+                        class={classes!("input", seed_error.map(|_| "is-danger"))}
+                        type="text"
+                        inputmode="numeric"
+                        placeholder={"Random Seed"}
+                        value={seed_text}
+                        oninput={on_seed_input}
+                    />
+                    if let Some(error) = seed_error {
+                        <p class={classes!("help", "is-danger")}>{error}</p>
+                    }
+                </div>
             </div>
 
+            if let Some(seed) = loadout.seed {
+                <p class={classes!("has-text-centered")}>{&format!("Seed: {seed}")}</p>
+            }
+
+            if let Some(error) = shared_error {
+                <p class={classes!("has-text-centered", "has-text-danger")}>{error}</p>
+            }
+
+            <p class={classes!("has-text-centered")}>
+                <Link<Route> to={Route::Loadout { code: share_code }}>{"Share Link"}</Link<Route>>
+            </p>
+
+            <div class={classes!("buttons", "is-centered")}>
+                <button
+                    class={classes!("button", "is-small")}
+                    onclick={on_copy_text_click(TextFormat::Plain)}
+                >{"Copy as Text"}</button>
+                <button
+                    class={classes!("button", "is-small")}
+                    onclick={on_copy_text_click(TextFormat::Markdown)}
+                >{"Copy as Markdown"}</button>
+            </div>
+
+            <LoadoutJsonBox
+                export={export_json}
+                problems={import_problems}
+                on_import={on_loadout_import}
+            />
+
+            {loadout_errors_html}
+
             <div class={classes!("loadout")}>
                 <div class={classes!("loadout-container", weapon_valid[0])}>
                     <WeaponSlot
@@ -836,6 +1217,7 @@ pub fn RandomLoadout() -> Html {
                         locked={loadout.weapon_one.locked}
                         dual_wield={loadout.weapon_one.item.as_ref().map_or(false, |weapon_one| weapon_one.dual_wield)}
                         ammo_types={loadout.weapon_one.item.as_ref().map_or_else(Vec::new, |weapon_one| weapon_one.ammo_equipped.clone())}
+                        errors={loadout.get_slot_errors(TransactionCategory::Weapon, 0)}
                         on_weapon_slot_clicked={on_weapon_one_clicked}
                         on_weapon_toggle_lock={on_weapon_one_toggle_lock}
                         on_weapon_delete={on_weapon_one_delete}
@@ -848,6 +1230,7 @@ pub fn RandomLoadout() -> Html {
                             id={0}
                             items={CORE_SEARCH_UTIL.weapons.clone()}
                             item_variant={ItemVariant::Weapon}
+                            max_rank={config.max_rank}
                             on_item_selected={on_weapon_one_selected}
                         />
                     }
@@ -861,6 +1244,7 @@ pub fn RandomLoadout() -> Html {
                         locked={loadout.weapon_two.locked}
                         dual_wield={loadout.weapon_two.item.as_ref().map_or(false, |weapon_two| weapon_two.dual_wield)}
                         ammo_types={loadout.weapon_two.item.as_ref().map_or_else(Vec::new, |weapon_two| weapon_two.ammo_equipped.clone())}
+                        errors={loadout.get_slot_errors(TransactionCategory::Weapon, 1)}
                         on_weapon_slot_clicked={on_weapon_two_clicked}
                         on_weapon_toggle_lock={on_weapon_two_toggle_lock}
                         on_weapon_delete={on_weapon_two_delete}
@@ -873,6 +1257,7 @@ pub fn RandomLoadout() -> Html {
                             id={0}
                             items={CORE_SEARCH_UTIL.weapons.clone()}
                             item_variant={ItemVariant::Weapon}
+                            max_rank={config.max_rank}
                             on_item_selected={on_weapon_two_selected}
                         />
                     }
@@ -889,6 +1274,7 @@ pub fn RandomLoadout() -> Html {
                                 tool={tool.item.clone()}
                                 locked={tool.locked}
                                 {id}
+                                errors={loadout.get_slot_errors(TransactionCategory::Tool, id.try_into().unwrap_or(0))}
                                 on_tool_slot_clicked={on_tool_clicked.clone()}
                                 on_tool_toggle_lock={on_tool_toggle_lock.clone()}
                                 on_tool_delete={on_tool_delete.clone()}
@@ -900,6 +1286,7 @@ pub fn RandomLoadout() -> Html {
                                     {id}
                                     items={CORE_SEARCH_UTIL.tools.clone()}
                                     item_variant={ItemVariant::Tool}
+                                    max_rank={config.max_rank}
                                     on_item_selected={on_tool_selected.clone()}
                                 />
                             }
@@ -916,6 +1303,7 @@ pub fn RandomLoadout() -> Html {
                                 consumable={consumable.item.clone()}
                                 locked={consumable.locked}
                                 {id}
+                                errors={loadout.get_slot_errors(TransactionCategory::Consumable, id.try_into().unwrap_or(0))}
                                 on_consumable_slot_clicked={on_consumable_clicked.clone()}
                                 on_consumable_toggle_lock={on_consumable_toggle_lock.clone()}
                                 on_consumable_delete={on_consumable_delete.clone()}
@@ -927,6 +1315,7 @@ pub fn RandomLoadout() -> Html {
                                     {id}
                                     items={CORE_SEARCH_UTIL.consumables.clone()}
                                     item_variant={ItemVariant::Consumable}
+                                    max_rank={config.max_rank}
                                     on_item_selected={on_consumable_selected.clone()}
                                 />
                             }
@@ -951,10 +1340,19 @@ pub fn RandomLoadout() -> Html {
         if advanced_options_toggled {
             <AdvancedOptions
                 is_active={advanced_options_toggled}
-                config={config}
+                config={config.clone()}
                 on_options_close={on_advanced_options_close}
             />
         }
+
+        if unlock_tracker_toggled {
+            <UnlockTracker
+                is_active={unlock_tracker_toggled}
+                unlocks={config.unlocks.clone()}
+                max_rank={config.max_rank}
+                on_unlocks_close={on_unlock_tracker_close}
+            />
+        }
         </>
     }
 }
@@ -1,5 +1,9 @@
 pub mod loadout;
 pub mod page_not_found;
+pub mod squad;
+pub mod unlock_planner;
 
 pub use loadout::RandomLoadout;
 pub use page_not_found::PageNotFound;
+pub use squad::SquadLoadouts;
+pub use unlock_planner::UnlockPlanner;
@@ -0,0 +1,295 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::components::{AdvancedOptions, SquadHunter};
+use crate::content::generic_item::GenericItemLockable;
+use crate::randomizer::{
+    budget::TransactionCategory,
+    squad::{self, Squad, SquadConfig, MAX_SQUAD_SIZE, MIN_SQUAD_SIZE},
+    Config, Loadout,
+};
+use crate::storage;
+
+fn slot_mut(
+    hunter: &mut Loadout,
+    category: TransactionCategory,
+    slot: u8,
+) -> Option<&mut GenericItemLockable> {
+    match category {
+        TransactionCategory::Weapon if slot == 0 => Some(&mut hunter.weapon_one),
+        TransactionCategory::Weapon if slot == 1 => Some(&mut hunter.weapon_two),
+        TransactionCategory::Tool => hunter.tools.get_mut(usize::from(slot)),
+        TransactionCategory::Consumable => hunter.consumables.get_mut(usize::from(slot)),
+        _ => None,
+    }
+}
+
+#[function_component]
+pub fn SquadLoadouts() -> Html {
+    // The squad uses the same rule set as the single loadout page.
+    let config_handle = use_state(|| Config {
+        unlocks: storage::load(storage::UNLOCKS_KEY).unwrap_or_default(),
+        ..storage::load(storage::CONFIG_KEY).unwrap_or_default()
+    });
+    let config = (*config_handle).clone();
+
+    let squad_config_handle =
+        use_state(|| storage::load::<SquadConfig>(storage::SQUAD_CONFIG_KEY).unwrap_or_default());
+    let squad_config = (*squad_config_handle).clone();
+
+    let squad_handle = use_state(|| {
+        storage::load::<Squad>(storage::SQUAD_KEY).unwrap_or_else(|| Squad::new(squad_config.size))
+    });
+    let squad = (*squad_handle).clone();
+
+    let on_size_changed = {
+        let squad_config_handle = squad_config_handle.clone();
+        let squad_handle = squad_handle.clone();
+        let squad_config = squad_config.clone();
+        let squad = squad.clone();
+
+        move |e: Event| {
+            let target: Option<EventTarget> = e.target();
+
+            let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+
+            if let Some(size) = select.and_then(|select| select.value().parse::<usize>().ok()) {
+                let mut squad_config = squad_config.clone();
+                let mut squad = squad.clone();
+
+                squad_config.size = size;
+                squad.resize(size);
+
+                squad_config_handle.set(squad_config);
+                squad_handle.set(squad);
+            }
+        }
+    };
+
+    let on_squad_option_toggle = |toggle: fn(&mut SquadConfig)| {
+        let squad_config_handle = squad_config_handle.clone();
+        let squad_config = squad_config.clone();
+
+        move |_| {
+            let mut squad_config = squad_config.clone();
+            toggle(&mut squad_config);
+            squad_config_handle.set(squad_config);
+        }
+    };
+
+    let on_balance_tolerance_input = {
+        let squad_config_handle = squad_config_handle.clone();
+        let squad_config = squad_config.clone();
+
+        move |e: InputEvent| {
+            let target: Option<EventTarget> = e.target();
+
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+            if let Some(input) = input {
+                let mut squad_config = squad_config.clone();
+
+                // Empty turns balancing off.
+                if input.value().is_empty() {
+                    squad_config.balance_tolerance = None;
+                } else {
+                    squad_config.balance_tolerance = input.value().parse::<u16>().ok();
+                }
+
+                squad_config_handle.set(squad_config);
+            }
+        }
+    };
+
+    let on_generate_squad_clicked = {
+        let squad_handle = squad_handle.clone();
+        let squad = squad.clone();
+        let config = config.clone();
+        let squad_config = squad_config.clone();
+
+        move |_: MouseEvent| {
+            let mut squad = squad.clone();
+            squad::random_squad(&mut squad, &config, &squad_config);
+            squad_handle.set(squad);
+        }
+    };
+
+    let on_toggle_lock = {
+        let squad = squad.clone();
+
+        Callback::from(
+            move |(hunter, category, slot): (usize, TransactionCategory, u8)| {
+                let mut squad = squad.clone();
+
+                if let Some(lockable) = squad
+                    .hunters
+                    .get_mut(hunter)
+                    .and_then(|hunter| slot_mut(hunter, category, slot))
+                {
+                    lockable.locked = !lockable.locked;
+                }
+
+                squad_handle.set(squad);
+            },
+        )
+    };
+
+    let advanced_options_toggled_handle = use_state(|| false);
+    let advanced_options_toggled = *advanced_options_toggled_handle;
+    let on_advanced_options_toggled = {
+        let advanced_options_toggled_handle = advanced_options_toggled_handle.clone();
+
+        move |_: MouseEvent| {
+            advanced_options_toggled_handle.set(!advanced_options_toggled);
+        }
+    };
+
+    let on_advanced_options_close = {
+        move |new_config: Config| {
+            advanced_options_toggled_handle.set(!advanced_options_toggled);
+            config_handle.set(new_config);
+        }
+    };
+
+    // Saved whenever one of them changes, the config is shared with the loadout page.
+    use_effect_with(
+        (config.clone(), squad_config.clone(), squad.clone()),
+        |(config, squad_config, squad)| {
+            storage::save(storage::CONFIG_KEY, config);
+            storage::save(storage::SQUAD_CONFIG_KEY, squad_config);
+            storage::save(storage::SQUAD_KEY, squad);
+        },
+    );
+
+    let size_options_html = (MIN_SQUAD_SIZE..=MAX_SQUAD_SIZE)
+        .map(|size| {
+            html! {
+                <option selected={size == squad.hunters.len()} value={size.to_string()}>
+                    {if size == 2 { "Duo" } else { "Trio" }}
+                </option>
+            }
+        })
+        .collect::<Html>();
+
+    let hunters_html = squad
+        .hunters
+        .iter()
+        .zip(&squad.budgets)
+        .enumerate()
+        .map(|(id, (hunter, budget))| {
+            html! {
+                <SquadHunter
+                    id={id}
+                    hunter={hunter.clone()}
+                    budget={budget.clone()}
+                    on_toggle_lock={on_toggle_lock.clone()}
+                />
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+        <div class={classes!("container", "my-4")}>
+            <div class={classes!("columns", "is-centered", "has-text-centered")}>
+                <div class={classes!("column")}>
+                    <label class="checkbox">
+                        <input
+                            type="checkbox"
+                            checked={squad_config.unique_primaries}
+                            onchange={on_squad_option_toggle(|squad_config| squad_config.unique_primaries = !squad_config.unique_primaries)}
+                        />
+                        {"No Duplicate Primaries"}
+                    </label>
+                </div>
+
+                <div class={classes!("column")}>
+                    <label class="checkbox">
+                        <input
+                            type="checkbox"
+                            checked={squad_config.shared_budget}
+                            onchange={on_squad_option_toggle(|squad_config| squad_config.shared_budget = !squad_config.shared_budget)}
+                        />
+                        {"Shared Team Budget"}
+                    </label>
+                </div>
+
+                <div class={classes!("column")}>
+                    <label class="checkbox">
+                        <input
+                            type="checkbox"
+                            checked={squad_config.require_support}
+                            onchange={on_squad_option_toggle(|squad_config| squad_config.require_support = !squad_config.require_support)}
+                        />
+                        {"Medkit or Long Range Gun"}
+                    </label>
+                </div>
+
+                <div class={classes!("column", "is-narrow")}>
+                    <input
+                        class={classes!("input")}
+                        type="number"
+                        min="0"
+                        placeholder={"Balance Tolerance"}
+                        value={squad_config.balance_tolerance.map_or_else(String::new, |tolerance| tolerance.to_string())}
+                        oninput={on_balance_tolerance_input}
+                    />
+                </div>
+            </div>
+
+            <div class={classes!("columns", "is-centered")}>
+                <div class={classes!("column", "is-narrow")}>
+                    <div class={classes!("select")}>
+                        <select onchange={on_size_changed}>
+                            {size_options_html}
+                        </select>
+                    </div>
+                </div>
+
+                <div class={classes!("column", "is-flex-grow-0")}>
+                    <button class="button" onclick={on_advanced_options_toggled}>
+                        {"Advanced Options"}
+                    </button>
+                </div>
+
+                <div class={classes!("column", "is-flex-grow-0")}>
+                    <button class="button" onclick={on_generate_squad_clicked}>
+                        {"Generate Squad"}
+                    </button>
+                </div>
+            </div>
+
+            if let Some(seed) = squad.seed {
+                <p class={classes!("has-text-centered")}>{&format!("Seed: {seed}")}</p>
+            }
+
+            <div class={classes!("columns")}>
+                {hunters_html}
+            </div>
+
+            <h2 class={classes!("is-size-2", "has-text-centered")}>
+                {&*format!("Team Hunt Dollars: {}", squad.total_cost())}
+            </h2>
+
+            <p class={classes!(
+                "has-text-centered",
+                squad_config
+                    .balance_tolerance
+                    .is_some_and(|tolerance| squad.cost_spread() > tolerance)
+                    .then_some("has-text-danger"),
+            )}>
+                {&format!("Cost Spread: {}", squad.cost_spread())}
+            </p>
+        </div>
+
+        if advanced_options_toggled {
+            <AdvancedOptions
+                is_active={advanced_options_toggled}
+                config={config}
+                on_options_close={on_advanced_options_close}
+            />
+        }
+        </>
+    }
+}
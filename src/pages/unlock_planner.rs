@@ -0,0 +1,208 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::prelude::*;
+
+use crate::content::{BulletVariant, GenericItem, CORE_SEARCH_UTIL};
+use crate::randomizer::{
+    unlock_path::{self, UnlockPath},
+    Unlocks,
+};
+use crate::storage;
+
+// The full catalog is a few hundred items, only show the first few matches.
+const MAX_SEARCH_RESULTS: usize = 30;
+
+#[derive(PartialEq, Eq, Properties)]
+pub struct UnlockPathDisplayProps {
+    pub path: UnlockPath,
+    pub unlocks: Unlocks,
+}
+
+#[function_component]
+pub fn UnlockPathDisplay(props: &UnlockPathDisplayProps) -> Html {
+    let UnlockPathDisplayProps { path, unlocks } = props;
+
+    let steps_html = path
+        .steps
+        .iter()
+        .map(|step| {
+            let requirements_html = step
+                .requirements
+                .iter()
+                .map(|requirement| html! { <li>{requirement.to_string()}</li> })
+                .collect::<Html>();
+
+            html! {
+                <li>
+                    <strong>{&format!("Unlock {}", step.unlock)}</strong>
+                    if unlocks.is_marked(&step.unlock) {
+                        <span class={classes!("has-text-success")}>{" (Unlocked)"}</span>
+                    }
+                    <ul>{requirements_html}</ul>
+                </li>
+            }
+        })
+        .collect::<Html>();
+
+    let dangling_html = path
+        .dangling
+        .iter()
+        .map(|previous| {
+            html! {
+                <p class={classes!("has-text-danger")}>
+                    {&format!("Unknown requirement, {previous} is not in the item data.")}
+                </p>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class={classes!("content")}>
+            <h4 class={classes!("is-size-4")}>
+                {&format!("Requires bloodline rank {}", path.get_rank())}
+            </h4>
+
+            <ol>{steps_html}</ol>
+
+            {dangling_html}
+        </div>
+    }
+}
+
+#[function_component]
+pub fn UnlockPlanner() -> Html {
+    let unlocks = use_state(|| storage::load::<Unlocks>(storage::UNLOCKS_KEY).unwrap_or_default());
+
+    let search_terms_handle = use_state(String::new);
+    let search_terms = (*search_terms_handle).to_lowercase();
+
+    let target_handle = use_state(|| None::<(GenericItem, Option<BulletVariant>)>);
+    let target = (*target_handle).clone();
+
+    let on_search_field_input = move |e: InputEvent| {
+        let target: Option<EventTarget> = e.target();
+
+        let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+        if let Some(input) = input {
+            search_terms_handle.set(input.value());
+        }
+    };
+
+    let search_results_html = CORE_SEARCH_UTIL
+        .weapons
+        .iter()
+        .chain(&CORE_SEARCH_UTIL.tools)
+        .chain(&CORE_SEARCH_UTIL.consumables)
+        .filter(|item| item.to_full_name().to_lowercase().contains(&search_terms))
+        .take(MAX_SEARCH_RESULTS)
+        .map(|item| {
+            let on_item_clicked = {
+                let target_handle = target_handle.clone();
+                let item = item.clone();
+
+                move |_: MouseEvent| {
+                    target_handle.set(Some((item.clone(), None)));
+                }
+            };
+
+            html! {
+                <a class={classes!("panel-block")} onclick={on_item_clicked}>
+                    {item.to_full_name()}
+                </a>
+            }
+        })
+        .collect::<Html>();
+
+    let target_html = target.map_or_else(
+        || html! { <p>{"Select an item to see how to unlock it."}</p> },
+        |(item, ammo)| {
+            // Ammo is unlocked on the base weapon so only offer it there.
+            let ammo_buttons_html = if item.variant.is_none() {
+                item.get_bullets()
+                    .into_iter()
+                    .filter_map(|bullet| bullet.name.clone())
+                    .map(|variant| {
+                        let is_selected = ammo.as_ref() == Some(&variant);
+
+                        let on_ammo_clicked = {
+                            let target_handle = target_handle.clone();
+                            let item = item.clone();
+                            let variant = variant.clone();
+
+                            move |_: MouseEvent| {
+                                target_handle.set(Some((item.clone(), Some(variant.clone()))));
+                            }
+                        };
+
+                        html! {
+                            <button
+                                class={classes!("button", "is-small", is_selected.then_some("is-primary"))}
+                                onclick={on_ammo_clicked}
+                            >
+                                {variant.to_string()}
+                            </button>
+                        }
+                    })
+                    .collect::<Html>()
+            } else {
+                html! {}
+            };
+
+            let path = ammo.as_ref().map_or_else(
+                || unlock_path::plan_item_unlock(&item),
+                |ammo| unlock_path::plan_ammo_unlock(&item, ammo),
+            );
+
+            html! {
+                <>
+                    <h3 class={classes!("is-size-3")}>{item.to_full_name()}</h3>
+                    <div class={classes!("buttons")}>{ammo_buttons_html}</div>
+                    <UnlockPathDisplay path={path} unlocks={(*unlocks).clone()} />
+                </>
+            }
+        },
+    );
+
+    // Dangling references anywhere in the data, these are usually typos in the json files.
+    let dangling_html = CORE_SEARCH_UTIL
+        .get_dangling_requirements()
+        .into_iter()
+        .map(|(name, previous)| {
+            html! {
+                <p class={classes!("has-text-danger")}>
+                    {&format!("{name} requires {previous} which is not in the item data.")}
+                </p>
+            }
+        })
+        .collect::<Vec<Html>>();
+
+    html! {
+        <div class={classes!("container", "my-4")}>
+            <div class={classes!("columns")}>
+                <div class={classes!("column", "is-one-third")}>
+                    <nav class={classes!("panel")}>
+                        <div class={classes!("panel-block")}>
+                            <input
+                                class={classes!("input")}
+                                type="text"
+                                placeholder="Search"
+                                oninput={on_search_field_input}
+                            />
+                        </div>
+                        {search_results_html}
+                    </nav>
+                </div>
+
+                <div class={classes!("column")}>
+                    {target_html}
+                </div>
+            </div>
+
+            if !dangling_html.is_empty() {
+                <h4 class={classes!("is-size-4")}>{"Item Data Issues"}</h4>
+                {dangling_html}
+            }
+        </div>
+    }
+}
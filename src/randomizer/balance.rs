@@ -0,0 +1,136 @@
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+use crate::randomizer::{loadout, Budget, Config, Loadout};
+
+// How many loadouts are regenerated trying to bring the costs within the tolerance before
+// settling for the closest set found.
+pub const MAX_BALANCE_ATTEMPTS: usize = 100;
+
+// The most loadouts one balanced generation makes, counting every attempt `loadout::random` can
+// make to reach the min cost. Retries inside retries add up quickly and this all runs in a single
+// call in the browser.
+pub const MAX_GENERATIONS: usize = 1000;
+
+// Generations left for one balanced generation.
+pub struct Work {
+    left: usize,
+}
+
+impl Default for Work {
+    fn default() -> Self {
+        Self {
+            left: MAX_GENERATIONS,
+        }
+    }
+}
+
+impl Work {
+    // Takes what generating a loadout with `config` can cost, false when nothing was left to take.
+    pub fn take(&mut self, config: &Config) -> bool {
+        if self.left == 0 {
+            return false;
+        }
+
+        self.left = self.left.saturating_sub(loadout::max_attempts(config));
+        true
+    }
+
+    #[must_use]
+    pub const fn is_spent(&self) -> bool {
+        self.left == 0
+    }
+}
+
+// Difference between the most and least expensive loadout.
+#[must_use]
+pub fn cost_spread(budgets: &[Budget]) -> u16 {
+    let costs = budgets.iter().map(|budget| budget.total_cost);
+
+    match (costs.clone().max(), costs.min()) {
+        (Some(max), Some(min)) => max - min,
+        _ => 0,
+    }
+}
+
+fn average_cost(budgets: &[Budget]) -> u32 {
+    let total = budgets
+        .iter()
+        .map(|budget| u32::from(budget.total_cost))
+        .sum::<u32>();
+
+    total / u32::try_from(budgets.len()).unwrap_or(1).max(1)
+}
+
+// Keeps rerolling whichever loadout is furthest from the average cost until the spread is within
+// `tolerance`, a reroll is only kept when it lands closer to the average. `reroll` is given the
+// position to reroll and the current loadouts, and returns `None` once it's out of work. Returns
+// the final spread.
+pub fn rebalance(
+    loadouts: &mut [Loadout],
+    budgets: &mut [Budget],
+    tolerance: u16,
+    rng: &mut StdRng,
+    mut reroll: impl FnMut(usize, &[Loadout], &[Budget], &mut StdRng) -> Option<(Loadout, Budget)>,
+) -> u16 {
+    for _ in 0..MAX_BALANCE_ATTEMPTS {
+        if cost_spread(budgets) <= tolerance {
+            break;
+        }
+
+        let average = average_cost(budgets);
+        let distance = |budget: &Budget| u32::from(budget.total_cost).abs_diff(average);
+
+        let Some(pos) = (0..budgets.len()).max_by_key(|pos| distance(&budgets[*pos])) else {
+            break;
+        };
+
+        let Some((loadout, budget)) = reroll(pos, loadouts, budgets, rng) else {
+            break;
+        };
+
+        if distance(&budget) < distance(&budgets[pos]) {
+            loadouts[pos] = loadout;
+            budgets[pos] = budget;
+        }
+    }
+
+    cost_spread(budgets)
+}
+
+// Generates every loadout, then rebalances them. Each reroll starts from the loadouts own locks,
+// returns the final spread.
+pub fn random_balanced(
+    loadouts: &mut [Loadout],
+    budgets: &mut [Budget],
+    config: &Config,
+    tolerance: u16,
+) -> u16 {
+    let seed = config.seed.unwrap_or_else(|| thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut work = Work::default();
+
+    let initial_loadouts = loadouts.to_vec();
+
+    let generate = |pos: usize, rng: &mut StdRng| {
+        let config = Config {
+            seed: Some(rng.gen()),
+            ..config.clone()
+        };
+
+        let mut loadout = initial_loadouts[pos].clone();
+        let mut budget = Budget::default();
+        loadout::random(&mut loadout, &mut budget, &config);
+
+        (loadout, budget)
+    };
+
+    // Every loadout is generated at least once no matter how much work that is.
+    for (pos, (loadout, budget)) in loadouts.iter_mut().zip(budgets.iter_mut()).enumerate() {
+        work.take(config);
+        (*loadout, *budget) = generate(pos, &mut rng);
+    }
+
+    rebalance(loadouts, budgets, tolerance, &mut rng, |pos, _, _, rng| {
+        work.take(config).then(|| generate(pos, rng))
+    })
+}
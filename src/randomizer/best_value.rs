@@ -0,0 +1,686 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use std::cmp::Ordering;
+
+use crate::content::{
+    generic_item::{CustomAmmo, GenericItemLockable},
+    GenericItem, Slot, WeaponSlotPreference, CORE_SEARCH_UTIL,
+};
+use crate::randomizer::{
+    budget::{self, TransactionCategory},
+    config::ToggleOption,
+    limits,
+    loadout::{self, Loadout},
+    Budget, Config,
+};
+
+// Keeps the most expensive option offered so far, options that cost the same are picked between
+// evenly so the same budget doesn't always produce the same loadout.
+struct BestPick<T> {
+    cost: u16,
+    ties: u32,
+    pick: Option<T>,
+}
+
+impl<T> BestPick<T> {
+    const fn new() -> Self {
+        Self {
+            cost: 0,
+            ties: 0,
+            pick: None,
+        }
+    }
+
+    fn offer(&mut self, rng: &mut StdRng, cost: u16, pick: impl FnOnce() -> T) {
+        match cost.cmp(&self.cost) {
+            Ordering::Greater => {
+                self.cost = cost;
+                self.ties = 1;
+                self.pick = Some(pick());
+            }
+            Ordering::Equal => {
+                self.ties += 1;
+
+                if rng.gen_range(0..self.ties) == 0 {
+                    self.pick = Some(pick());
+                }
+            }
+            Ordering::Less => {}
+        }
+    }
+}
+
+fn get_cost(weapon: &GenericItem) -> u16 {
+    weapon
+        .ammo_equipped
+        .iter()
+        .fold(weapon.get_cost(), |cost, ammo| cost.saturating_add(ammo.2))
+}
+
+// Every way the ammo slots of a weapon can be filled, the order of the slots doesn't matter.
+fn get_ammo_options(config: &Config, weapon: &GenericItem) -> Vec<Vec<CustomAmmo>> {
+    let Some(bullet_size) = weapon.get_bullet_size() else {
+        return vec![vec![]];
+    };
+
+    let custom_ammo = if config.option_exists(ToggleOption::CustomAmmo) {
+        weapon
+            .get_bullet_variants()
+            .into_iter()
+            .filter(|ammo_type| config.ammo_allowed(weapon, ammo_type))
+            .collect::<Vec<CustomAmmo>>()
+    } else {
+        vec![]
+    };
+    let custom_ammo = config.theme_ammo(weapon, custom_ammo);
+    let always_custom_ammo = config.option_exists(ToggleOption::AlwaysCustomAmmo)
+        || config.theme_needs_ammo(weapon, &custom_ammo);
+
+    let mut choices = vec![];
+
+    if custom_ammo.is_empty() || !always_custom_ammo {
+        choices.push((bullet_size, None, 0));
+    }
+
+    choices.extend(custom_ammo);
+
+    if weapon.additional_ammo_slots.unwrap_or(false) {
+        choices
+            .iter()
+            .enumerate()
+            .flat_map(|(pos, first)| {
+                choices[pos..]
+                    .iter()
+                    .map(|second| vec![first.clone(), second.clone()])
+            })
+            .collect()
+    } else {
+        choices.into_iter().map(|ammo| vec![ammo]).collect()
+    }
+}
+
+// Every weapon that could go in an unlocked slot, with dual wield and ammo already picked.
+fn get_weapon_options(config: &Config) -> Vec<GenericItem> {
+    let dual_wield = config.option_exists(ToggleOption::DualWield);
+    let always_dual_wield = dual_wield && config.option_exists(ToggleOption::AlwaysDualWield);
+
+    let mut options = vec![];
+
+    // Chances don't weigh anything when every option is searched, but a size at 0 is still never
+    // picked.
+    let weapons = CORE_SEARCH_UTIL
+        .weapons
+        .iter()
+        .filter(|weapon| config.item_allowed(weapon) && config.get_weapon_chance(weapon) > 0.0)
+        .collect::<Vec<&GenericItem>>();
+
+    for weapon in config.theme_items(weapons) {
+        let dual_wield_options: &[bool] = match (weapon.can_dual_wield(), always_dual_wield) {
+            (true, true) => &[true],
+            (false, true) => &[],
+            (true, false) if dual_wield => &[false, true],
+            _ => &[false],
+        };
+
+        for dual_wield in dual_wield_options {
+            for ammo_equipped in get_ammo_options(config, weapon) {
+                let mut option = weapon.clone();
+                option.dual_wield = *dual_wield;
+                option.ammo_equipped = ammo_equipped;
+                options.push(option);
+            }
+        }
+    }
+
+    options
+}
+
+fn same_weapon(weapon_one: &GenericItem, weapon_two: &GenericItem) -> bool {
+    weapon_one.name == weapon_two.name && weapon_one.variant == weapon_two.variant
+}
+
+// `strict` also requires the "always" options, those are dropped when nothing fits them.
+fn weapons_valid(
+    config: &Config,
+    strict: bool,
+    weapon_one: &GenericItem,
+    weapon_two: &GenericItem,
+) -> bool {
+    let quartermaster = config.option_exists(ToggleOption::Quartermaster);
+    let duplicates = config.option_exists(ToggleOption::DuplicateWeapons);
+
+    if !loadout::get_valid_slots(quartermaster, &weapon_one.get_slot())
+        .contains(&weapon_two.get_slot())
+    {
+        return false;
+    }
+
+    if !duplicates && same_weapon(weapon_one, weapon_two) {
+        return false;
+    }
+
+    if !strict {
+        return true;
+    }
+
+    if quartermaster && config.option_exists(ToggleOption::AlwaysQuartermaster) {
+        let slots = (weapon_one.get_slot(), weapon_two.get_slot());
+
+        if !matches!(
+            slots,
+            (Slot::Large, Slot::Medium) | (Slot::Medium, Slot::Large)
+        ) {
+            return false;
+        }
+    }
+
+    if duplicates
+        && config.option_exists(ToggleOption::AlwaysDuplicateWeapons)
+        && (!same_weapon(weapon_one, weapon_two) || weapon_one.dual_wield != weapon_two.dual_wield)
+    {
+        return false;
+    }
+
+    true
+}
+
+// Options for an unlocked slot, with `preferred` only the ones fitting the slot preference unless
+// none of them fit the budget.
+fn slot_options<'a>(
+    config: &Config,
+    options: &'a [(u16, GenericItem)],
+    slot: u8,
+    preferred: bool,
+) -> Vec<&'a (u16, GenericItem)> {
+    let fitting = options
+        .iter()
+        .filter(|(_, option)| !preferred || loadout::weapon_preferred(config, slot, option))
+        .collect::<Vec<&(u16, GenericItem)>>();
+
+    if fitting.is_empty() {
+        options.iter().collect()
+    } else {
+        fitting
+    }
+}
+
+fn best_weapons(
+    config: &Config,
+    rng: &mut StdRng,
+    budget: u16,
+    weapon_one: &GenericItemLockable,
+    weapon_two: &GenericItemLockable,
+    strict: bool,
+    preferred: bool,
+) -> BestPick<[Option<GenericItem>; 2]> {
+    // Most expensive first, so pairs can stop once they can't reach the best cost found.
+    let mut options = get_weapon_options(config)
+        .into_iter()
+        .map(|option| (get_cost(&option), option))
+        .filter(|(cost, _)| *cost <= budget)
+        .collect::<Vec<(u16, GenericItem)>>();
+    options.sort_by(|(cost_one, _), (cost_two, _)| cost_two.cmp(cost_one));
+
+    let options_one = slot_options(config, &options, 0, preferred);
+    let options_two = slot_options(config, &options, 1, preferred);
+
+    let mut best = BestPick::new();
+
+    match (weapon_one.locked, weapon_two.locked) {
+        (true, true) => {}
+        (false, false) => {
+            // With the same options in both slots the slot rules are the same both ways around,
+            // so each pair only needs checking once.
+            let symmetric =
+                !preferred || config.weapon_preferences[0] == config.weapon_preferences[1];
+
+            for (pos, (cost_one, option_one)) in options_one.iter().enumerate() {
+                let seconds = if symmetric {
+                    &options_two[pos..]
+                } else {
+                    &options_two[..]
+                };
+
+                let Some((max_two, _)) = seconds.first() else {
+                    break;
+                };
+
+                if best.pick.is_some() && cost_one.saturating_add(*max_two) < best.cost {
+                    break;
+                }
+
+                for (cost_two, option_two) in seconds {
+                    let cost = cost_one.saturating_add(*cost_two);
+
+                    if best.pick.is_some() && cost < best.cost {
+                        break;
+                    }
+
+                    if cost <= budget && weapons_valid(config, strict, option_one, option_two) {
+                        best.offer(rng, cost, || {
+                            [Some(option_one.clone()), Some(option_two.clone())]
+                        });
+                    }
+                }
+            }
+        }
+        (locked_one, _) => {
+            let (locked, options) = if locked_one {
+                (&weapon_one.item, &options_two)
+            } else {
+                (&weapon_two.item, &options_one)
+            };
+
+            // An empty locked slot doesn't limit what goes in the other one.
+            for (cost, option) in options.iter().filter(|(_, option)| {
+                locked
+                    .as_ref()
+                    .is_none_or(|locked| weapons_valid(config, strict, locked, option))
+            }) {
+                best.offer(rng, *cost, || [Some(option.clone()), None]);
+            }
+        }
+    }
+
+    best
+}
+
+fn pay_locked_weapon(
+    loadout: &mut Loadout,
+    budget: &mut Budget,
+    config: &Config,
+    rng: &mut StdRng,
+    slot: u8,
+    weapon: &mut GenericItemLockable,
+    check: &GenericItemLockable,
+) {
+    loadout::initial_weapon(loadout, budget, config, rng, slot, weapon, check);
+    loadout::custom_ammo(
+        budget,
+        &mut loadout.errors,
+        config,
+        rng,
+        slot,
+        weapon,
+        false,
+    );
+}
+
+fn fill_weapons(loadout: &mut Loadout, budget: &mut Budget, config: &Config, rng: &mut StdRng) {
+    // Locked weapons are paid for first the same way `loadout::random` does.
+    let mut weapon_one = loadout.weapon_one.clone();
+    let mut weapon_two = loadout.weapon_two.clone();
+
+    if weapon_one.locked {
+        pay_locked_weapon(
+            loadout,
+            budget,
+            config,
+            rng,
+            0,
+            &mut weapon_one,
+            &weapon_two,
+        );
+    }
+
+    if weapon_two.locked {
+        pay_locked_weapon(
+            loadout,
+            budget,
+            config,
+            rng,
+            1,
+            &mut weapon_two,
+            &weapon_one,
+        );
+    }
+
+    // The "always" options are dropped before the slot preferences when nothing fits both.
+    let mut best = BestPick::new();
+
+    for (preferred, strict) in [(true, true), (true, false), (false, true), (false, false)] {
+        best = best_weapons(
+            config,
+            rng,
+            budget.weapons_budget,
+            &weapon_one,
+            &weapon_two,
+            strict,
+            preferred,
+        );
+
+        if best.pick.is_some() {
+            break;
+        }
+    }
+
+    let mut picked = best.pick.unwrap_or_default().into_iter().flatten();
+
+    for (slot, weapon) in [(0, &mut weapon_one), (1, &mut weapon_two)] {
+        if !weapon.locked {
+            weapon.item = picked.next();
+            loadout::purchase_item(
+                budget,
+                &mut loadout.errors,
+                TransactionCategory::Weapon,
+                slot,
+                weapon,
+            );
+        }
+    }
+
+    loadout.weapon_one = weapon_one;
+    loadout.weapon_two = weapon_two;
+}
+
+// Finds the most expensive way to fill a category's unlocked slots, a branch and bound search
+// over the options of each slot with empty slots always allowed.
+struct ItemSearch<'a> {
+    // Every option once, slots refer to them by position so duplicates are cheap to spot.
+    items: Vec<&'a GenericItem>,
+    // Options for each slot as `(cost, position in items)`, `None` is leaving the slot empty.
+    slots: Vec<Vec<Option<(u16, usize)>>>,
+    // Maximums each item counts towards and the room left under each of them.
+    item_limits: Vec<Vec<usize>>,
+    rooms: Vec<usize>,
+    // `best_within[slot][cost]` is the most slots `slot..` can add up to without going over
+    // `cost`, ignoring duplicates and limits. It's an upper bound for what a branch can still
+    // reach, and exact when nothing else gets in the way.
+    best_within: Vec<Vec<u16>>,
+    budget: u16,
+    distinct: bool,
+}
+
+impl<'a> ItemSearch<'a> {
+    fn new(
+        rng: &mut StdRng,
+        budget: u16,
+        slots: &[Vec<&'a GenericItem>],
+        max_limits: &limits::MaxLimits,
+        distinct: bool,
+    ) -> Self {
+        let mut items: Vec<&GenericItem> = vec![];
+
+        let slots = slots
+            .iter()
+            .map(|options| {
+                let mut options = options
+                    .iter()
+                    .filter(|option| option.get_cost() <= budget)
+                    .map(|option| {
+                        let pos =
+                            items
+                                .iter()
+                                .position(|item| item == option)
+                                .unwrap_or_else(|| {
+                                    items.push(option);
+                                    items.len() - 1
+                                });
+
+                        Some((option.get_cost(), pos))
+                    })
+                    .collect::<Vec<Option<(u16, usize)>>>();
+
+                // Searching in a random order is what makes loadouts with the same cost vary.
+                options.push(None);
+                options.shuffle(rng);
+                options
+            })
+            .collect::<Vec<Vec<Option<(u16, usize)>>>>();
+
+        // Nothing can cost more than the most expensive option in every slot.
+        let cap = slots
+            .iter()
+            .map(|options| {
+                options
+                    .iter()
+                    .flatten()
+                    .map(|(cost, _)| *cost)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .fold(0, u16::saturating_add)
+            .min(budget);
+
+        let mut best_within = vec![vec![0; usize::from(cap) + 1]];
+        let mut reachable = vec![true; 1];
+        reachable.resize(usize::from(cap) + 1, false);
+
+        for options in slots.iter().rev() {
+            let next = (0..=usize::from(cap))
+                .map(|cost| {
+                    options.iter().any(|option| match option {
+                        None => reachable[cost],
+                        Some((option_cost, _)) => cost
+                            .checked_sub(usize::from(*option_cost))
+                            .is_some_and(|rest| reachable[rest]),
+                    })
+                })
+                .collect::<Vec<bool>>();
+
+            let mut within = Vec::with_capacity(next.len());
+            let mut highest = 0;
+
+            for (cost, is_reachable) in (0..).zip(&next) {
+                if *is_reachable {
+                    highest = cost;
+                }
+
+                within.push(highest);
+            }
+
+            best_within.push(within);
+            reachable = next;
+        }
+
+        best_within.reverse();
+
+        Self {
+            item_limits: items.iter().map(|item| max_limits.matching(item)).collect(),
+            rooms: max_limits.rooms(),
+            items,
+            slots,
+            best_within,
+            budget,
+            distinct,
+        }
+    }
+
+    fn bound(&self, slot: usize, cost: u16) -> u16 {
+        let within = &self.best_within[slot];
+        let remaining = usize::from(self.budget - cost).min(within.len() - 1);
+
+        cost + within[remaining]
+    }
+
+    // Returns true once a pick reaches `target`, nothing can cost more so the search stops there.
+    fn search(
+        &self,
+        slot: usize,
+        cost: u16,
+        target: u16,
+        picked: &mut Vec<Option<usize>>,
+        counts: &mut [usize],
+        best: &mut Option<(u16, Vec<Option<usize>>)>,
+    ) -> bool {
+        let Some(options) = self.slots.get(slot) else {
+            if best.as_ref().is_none_or(|(best_cost, _)| cost > *best_cost) {
+                *best = Some((cost, picked.clone()));
+            }
+
+            return cost == target;
+        };
+
+        if best
+            .as_ref()
+            .is_some_and(|(best_cost, _)| self.bound(slot, cost) <= *best_cost)
+        {
+            return false;
+        }
+
+        for option in options {
+            let Some((option_cost, pos)) = *option else {
+                picked.push(None);
+
+                if self.search(slot + 1, cost, target, picked, counts, best) {
+                    return true;
+                }
+
+                picked.pop();
+                continue;
+            };
+
+            let fits = cost
+                .checked_add(option_cost)
+                .is_some_and(|total| total <= self.budget)
+                && !(self.distinct && picked.contains(&Some(pos)))
+                && self.item_limits[pos]
+                    .iter()
+                    .all(|limit| counts[*limit] < self.rooms[*limit]);
+
+            if !fits {
+                continue;
+            }
+
+            for limit in &self.item_limits[pos] {
+                counts[*limit] += 1;
+            }
+            picked.push(Some(pos));
+
+            if self.search(slot + 1, cost + option_cost, target, picked, counts, best) {
+                return true;
+            }
+
+            picked.pop();
+            for limit in &self.item_limits[pos] {
+                counts[*limit] -= 1;
+            }
+        }
+
+        false
+    }
+
+    fn best(&self) -> Vec<Option<GenericItem>> {
+        let mut best = None;
+
+        self.search(
+            0,
+            0,
+            self.bound(0, 0),
+            &mut vec![],
+            &mut vec![0; self.rooms.len()],
+            &mut best,
+        );
+
+        best.map(|(_, picked)| {
+            picked
+                .into_iter()
+                .map(|pos| pos.map(|pos| self.items[pos].clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+    }
+}
+
+fn fill_tools(loadout: &mut Loadout, budget: &mut Budget, config: &Config, rng: &mut StdRng) {
+    let locked_tools = loadout
+        .tools
+        .iter()
+        .filter(|tool| tool.locked)
+        .filter_map(|tool| tool.item.clone())
+        .collect::<Vec<GenericItem>>();
+
+    let mut slots = loadout
+        .tools
+        .iter()
+        .enumerate()
+        .filter(|(_, tool)| !tool.locked)
+        .map(|(slot, _)| {
+            let tools = CORE_SEARCH_UTIL
+                .get_tools_by_preference(config.tool_preferences[slot])
+                .into_iter()
+                .filter(|tool| config.item_allowed(tool) && !locked_tools.contains(tool))
+                .collect::<Vec<&GenericItem>>();
+
+            config.theme_items(tools)
+        })
+        .collect::<Vec<Vec<&GenericItem>>>();
+    limits::reserve_needed(config, loadout, &mut slots);
+    let max_limits = limits::MaxLimits::new(config, loadout);
+    let search = ItemSearch::new(rng, budget.tools_budget, &slots, &max_limits, true);
+
+    let mut picked = search.best().into_iter();
+
+    for (slot, tool) in (0..).zip(loadout.tools.iter_mut()) {
+        if tool.locked {
+            continue;
+        }
+
+        tool.item = picked.next().flatten();
+        loadout::purchase_item(
+            budget,
+            &mut loadout.errors,
+            TransactionCategory::Tool,
+            slot,
+            tool,
+        );
+    }
+}
+
+fn fill_consumables(loadout: &mut Loadout, budget: &mut Budget, config: &Config, rng: &mut StdRng) {
+    let mut slots = loadout
+        .consumables
+        .iter()
+        .enumerate()
+        .filter(|(_, consumable)| !consumable.locked)
+        .map(|(slot, _)| {
+            let consumables = CORE_SEARCH_UTIL
+                .get_consumables_by_preference(config.consumable_preferences[slot])
+                .into_iter()
+                .filter(|consumable| config.item_allowed(consumable))
+                .collect::<Vec<&GenericItem>>();
+
+            config.theme_items(consumables)
+        })
+        .collect::<Vec<Vec<&GenericItem>>>();
+    limits::reserve_needed(config, loadout, &mut slots);
+    let max_limits = limits::MaxLimits::new(config, loadout);
+    let search = ItemSearch::new(rng, budget.consumables_budget, &slots, &max_limits, false);
+
+    let mut picked = search.best().into_iter();
+
+    for (slot, consumable) in (0..).zip(loadout.consumables.iter_mut()) {
+        if consumable.locked {
+            continue;
+        }
+
+        consumable.item = picked.next().flatten();
+        loadout::purchase_item(
+            budget,
+            &mut loadout.errors,
+            TransactionCategory::Consumable,
+            slot,
+            consumable,
+        );
+    }
+}
+
+// Fills every unlocked slot so the loadout costs as close to the budget as possible. Each category
+// is searched in full instead of drawing random items, weapons first then tools then consumables so
+// leftovers move down the same way they do in `loadout::random`. Expects the budget to be reset and
+// locked tools and consumables to already be paid for.
+pub fn best_value(loadout: &mut Loadout, budget: &mut Budget, config: &Config, rng: &mut StdRng) {
+    fill_weapons(loadout, budget, config, rng);
+
+    budget::transfer_weapons_to_tools(budget);
+
+    fill_tools(loadout, budget, config, rng);
+
+    budget::transfer_tools_to_consumables(budget);
+
+    fill_consumables(loadout, budget, config, rng);
+
+    // Sorting would move weapons out of the slot they were picked for.
+    if config.weapon_preferences == [WeaponSlotPreference::NoPreference; 2] {
+        loadout::sort_weapons(loadout, budget);
+    }
+}
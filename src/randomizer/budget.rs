@@ -1,47 +1,203 @@
-// checked_add_signed is experimental, so until it's pushed to the stable branch we can just use
-// bool as refund (true) or purchase (false) and u16 as the amount.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Transaction {
-    Bullet(bool, u16, String),
-    Tool(bool, u16, String),
-    Consumable(bool, u16, String),
-    Weapon(bool, u16, String),
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TransactionCategory {
+    Weapon,
+    Bullet,
+    Tool,
+    Consumable,
+}
+
+impl fmt::Display for TransactionCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Weapon => "Weapons",
+                Self::Bullet => "Ammo",
+                Self::Tool => "Tools",
+                Self::Consumable => "Consumables",
+            }
+        )
+    }
+}
+
+// A single entry in the budget ledger.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Transaction {
+    pub category: TransactionCategory,
+    // Purchases are positive and refunds are negative.
+    pub amount: i32,
+    // Slot of the item in its part of the loadout, ammo uses the slot of the weapon it's for.
+    pub slot: u8,
+    pub item: String,
 }
 
+impl Transaction {
+    #[must_use]
+    pub fn purchase(category: TransactionCategory, slot: u8, amount: u16, item: String) -> Self {
+        Self {
+            category,
+            amount: i32::from(amount),
+            slot,
+            item,
+        }
+    }
+
+    #[must_use]
+    pub fn refund(category: TransactionCategory, slot: u8, amount: u16, item: String) -> Self {
+        Self {
+            category,
+            amount: -i32::from(amount),
+            slot,
+            item,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_refund(&self) -> bool {
+        self.amount < 0
+    }
+
+    #[must_use]
+    pub fn get_amount(&self) -> u16 {
+        u16::try_from(self.amount.unsigned_abs()).unwrap_or(u16::MAX)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransactionResult {
-    InsufficientFunds,
+    // How much more money the purchase needed.
+    InsufficientFunds(u16),
     ErrorBudgeting,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+// Percentages of the initial budget given to each category, these have to add up to 100.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BudgetSplit {
+    pub weapons: u8,
+    pub tools: u8,
+    pub consumables: u8,
+}
+
+// The closest whole percentages to the original split, where a custom split starts from.
+impl Default for BudgetSplit {
+    fn default() -> Self {
+        Self {
+            weapons: 59,
+            tools: 16,
+            consumables: 25,
+        }
+    }
+}
+
+impl BudgetSplit {
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        u16::from(self.weapons) + u16::from(self.tools) + u16::from(self.consumables) == 100
+    }
+}
+
+// What happens to money a category doesn't spend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum LeftoverPolicy {
+    // Weapon leftovers go to tools, and tool leftovers go to consumables.
+    #[default]
+    Cascade,
+    // There are no categories, everything is bought from the full budget and the split is ignored.
+    Pool,
+    // Every category is capped to its split, leftovers are never spent.
+    Strict,
+}
+
+impl fmt::Display for LeftoverPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Cascade => "Cascade",
+                Self::Pool => "Pool",
+                Self::Strict => "Strict",
+            }
+        )
+    }
+}
+
+impl TryFrom<String> for LeftoverPolicy {
+    type Error = &'static str;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match &*val {
+            "Cascade" => Ok(Self::Cascade),
+            "Pool" => Ok(Self::Pool),
+            "Strict" => Ok(Self::Strict),
+            _ => Err("Invalid leftover policy"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Budget {
-    pub debug_transactions: Vec<Transaction>,
+    // Every purchase and refund since the budget was last reset, in order.
+    pub transactions: Vec<Transaction>,
     pub total_cost: u16,
     pub initial_budget: u16,
     pub tools_budget: u16,
     pub consumables_budget: u16,
     pub weapons_budget: u16,
+    // `None` is the original split, a sixth for tools and a quarter for consumables with weapons
+    // getting the rest.
+    pub split: Option<BudgetSplit>,
+    pub leftover_policy: LeftoverPolicy,
 }
 
 impl Default for Budget {
     fn default() -> Self {
         Self {
-            debug_transactions: vec![],
+            transactions: vec![],
             total_cost: 0,
             initial_budget: u16::MAX,
             tools_budget: u16::MAX,
             consumables_budget: u16::MAX,
             weapons_budget: u16::MAX,
+            split: None,
+            leftover_policy: LeftoverPolicy::default(),
         }
     }
 }
 
+fn percent_of(amount: u16, percent: u8) -> u16 {
+    u16::try_from(u32::from(amount) * u32::from(percent) / 100).unwrap_or(u16::MAX)
+}
+
 fn reset_budget(budget: &mut Budget) -> Result<(), TransactionResult> {
     budget.total_cost = 0;
-    budget.tools_budget = budget.initial_budget / 6;
-    budget.consumables_budget = budget.initial_budget / 4;
-    budget.debug_transactions.clear();
+    budget.transactions.clear();
+
+    if budget.leftover_policy == LeftoverPolicy::Pool {
+        budget.tools_budget = budget.initial_budget;
+        budget.consumables_budget = budget.initial_budget;
+        budget.weapons_budget = budget.initial_budget;
+
+        return Ok(());
+    }
 
+    match &budget.split {
+        None => {
+            budget.tools_budget = budget.initial_budget / 6;
+            budget.consumables_budget = budget.initial_budget / 4;
+        }
+        Some(split) if split.is_valid() => {
+            budget.tools_budget = percent_of(budget.initial_budget, split.tools);
+            budget.consumables_budget = percent_of(budget.initial_budget, split.consumables);
+        }
+        Some(_) => return Err(TransactionResult::ErrorBudgeting),
+    }
+
+    // Weapons get whatever is left so rounding never loses any money.
     match budget.initial_budget.checked_sub(
         budget
             .tools_budget
@@ -56,85 +212,250 @@ fn reset_budget(budget: &mut Budget) -> Result<(), TransactionResult> {
 }
 
 pub fn transfer_weapons_to_tools(budget: &mut Budget) {
-    budget.tools_budget = budget.tools_budget.saturating_add(budget.weapons_budget);
+    if budget.leftover_policy == LeftoverPolicy::Cascade {
+        budget.tools_budget = budget.tools_budget.saturating_add(budget.weapons_budget);
+    }
 }
 
 pub fn transfer_tools_to_consumables(budget: &mut Budget) {
-    budget.consumables_budget = budget
-        .consumables_budget
-        .saturating_add(budget.tools_budget);
+    if budget.leftover_policy == LeftoverPolicy::Cascade {
+        budget.consumables_budget = budget
+            .consumables_budget
+            .saturating_add(budget.tools_budget);
+    }
 }
 
+/// Resets the budget to `new_budget` split between the categories.
+///
+/// # Errors
+///
+/// `TransactionResult::ErrorBudgeting` when the custom split doesn't add up to 100.
 pub fn set_budget(budget: &mut Budget, new_budget: u16) -> Result<(), TransactionResult> {
     budget.initial_budget = new_budget;
 
     reset_budget(budget)
 }
 
+impl Budget {
+    // What has been spent on a category after refunds.
+    #[must_use]
+    pub fn get_subtotal(&self, category: TransactionCategory) -> i32 {
+        self.transactions
+            .iter()
+            .filter(|transaction| transaction.category == category)
+            .map(|transaction| transaction.amount)
+            .sum()
+    }
+}
+
+/// Records a purchase or refund against its category.
+///
+/// # Errors
+///
+/// `TransactionResult::InsufficientFunds` with the shortfall when the category can't afford a
+/// purchase, nothing is recorded then.
 pub fn process_transaction(
     budget: &mut Budget,
-    transaction_type: Transaction,
+    transaction: Transaction,
 ) -> Result<(), TransactionResult> {
-    match transaction_type {
-        Transaction::Bullet(refund, amount, _) | Transaction::Weapon(refund, amount, _) => {
-            if refund {
-                // Since it's not an error to have too much money we just set it to max if we
-                // overflow.
-                budget.weapons_budget = budget.weapons_budget.saturating_add(amount);
-                budget.total_cost = budget.total_cost.saturating_sub(amount);
-
-                Ok(())
-            } else {
-                match budget.weapons_budget.checked_sub(amount) {
-                    Some(val) => {
-                        budget.weapons_budget = val;
-                        budget.total_cost = budget.total_cost.saturating_add(amount);
-                        budget.debug_transactions.push(transaction_type);
-
-                        Ok(())
-                    }
-                    // We underflowed, don't do the calculation, not enough money.
-                    None => Err(TransactionResult::InsufficientFunds),
-                }
+    let amount = transaction.get_amount();
+
+    let category_budget = match transaction.category {
+        TransactionCategory::Bullet | TransactionCategory::Weapon => budget.weapons_budget,
+        TransactionCategory::Tool => budget.tools_budget,
+        TransactionCategory::Consumable => budget.consumables_budget,
+    };
+
+    let new_category_budget = if transaction.is_refund() {
+        // Since it's not an error to have too much money we just set it to max if we overflow.
+        budget.total_cost = budget.total_cost.saturating_sub(amount);
+
+        category_budget.saturating_add(amount)
+    } else {
+        // We underflowed, don't do the calculation, not enough money.
+        let Some(val) = category_budget.checked_sub(amount) else {
+            return Err(TransactionResult::InsufficientFunds(
+                amount.saturating_sub(category_budget),
+            ));
+        };
+
+        budget.total_cost = budget.total_cost.saturating_add(amount);
+
+        val
+    };
+
+    // Pooled categories all share the same money so every one of them changes together.
+    if budget.leftover_policy == LeftoverPolicy::Pool {
+        budget.weapons_budget = new_category_budget;
+        budget.tools_budget = new_category_budget;
+        budget.consumables_budget = new_category_budget;
+    } else {
+        match transaction.category {
+            TransactionCategory::Bullet | TransactionCategory::Weapon => {
+                budget.weapons_budget = new_category_budget;
             }
+            TransactionCategory::Tool => budget.tools_budget = new_category_budget,
+            TransactionCategory::Consumable => budget.consumables_budget = new_category_budget,
         }
-        Transaction::Consumable(refund, amount, _) => {
-            if refund {
-                budget.consumables_budget = budget.consumables_budget.saturating_add(amount);
-                budget.total_cost = budget.total_cost.saturating_sub(amount);
-
-                Ok(())
-            } else {
-                match budget.consumables_budget.checked_sub(amount) {
-                    Some(val) => {
-                        budget.consumables_budget = val;
-                        budget.total_cost = budget.total_cost.saturating_add(amount);
-                        budget.debug_transactions.push(transaction_type);
-
-                        Ok(())
-                    }
-                    None => Err(TransactionResult::InsufficientFunds),
-                }
-            }
+    }
+
+    budget.transactions.push(transaction);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::{loadout::LoadoutError, test_util::random_loadout, Config};
+
+    const CATEGORIES: [TransactionCategory; 4] = [
+        TransactionCategory::Weapon,
+        TransactionCategory::Bullet,
+        TransactionCategory::Tool,
+        TransactionCategory::Consumable,
+    ];
+
+    #[test]
+    fn subtotals_add_up_to_total_cost() {
+        let (_, budget) = random_loadout(&Config {
+            seed: Some(0),
+            max_cost: Some(1500),
+            ..Config::default()
+        });
+
+        let subtotals = CATEGORIES
+            .iter()
+            .map(|category| budget.get_subtotal(*category))
+            .sum::<i32>();
+
+        assert!(!budget.transactions.is_empty());
+        assert_eq!(subtotals, i32::from(budget.total_cost));
+    }
+
+    #[test]
+    fn refunds_are_recorded() {
+        let mut budget = Budget::default();
+        set_budget(&mut budget, 1000).unwrap();
+        let tools_budget = budget.tools_budget;
+
+        for transaction in [
+            Transaction::purchase(TransactionCategory::Tool, 1, 30, "Tool".to_string()),
+            Transaction::refund(TransactionCategory::Tool, 1, 30, "Tool".to_string()),
+        ] {
+            process_transaction(&mut budget, transaction).unwrap();
         }
-        Transaction::Tool(refund, amount, _) => {
-            if refund {
-                budget.tools_budget = budget.tools_budget.saturating_sub(amount);
-                budget.total_cost = budget.total_cost.saturating_sub(amount);
-
-                Ok(())
-            } else {
-                match budget.tools_budget.checked_sub(amount) {
-                    Some(val) => {
-                        budget.tools_budget = val;
-                        budget.total_cost = budget.total_cost.saturating_add(amount);
-                        budget.debug_transactions.push(transaction_type);
-
-                        Ok(())
-                    }
-                    None => Err(TransactionResult::InsufficientFunds),
-                }
-            }
+
+        assert_eq!(budget.transactions.len(), 2);
+        assert!(budget.transactions[1].is_refund());
+        assert_eq!(budget.get_subtotal(TransactionCategory::Tool), 0);
+        assert_eq!(budget.total_cost, 0);
+        assert_eq!(budget.tools_budget, tools_budget);
+    }
+
+    #[test]
+    fn failed_purchases_are_not_recorded() {
+        let mut budget = Budget::default();
+        set_budget(&mut budget, 120).unwrap();
+
+        assert_eq!(
+            process_transaction(
+                &mut budget,
+                Transaction::purchase(TransactionCategory::Tool, 0, 30, "Tool".to_string()),
+            ),
+            Err(TransactionResult::InsufficientFunds(10))
+        );
+        assert!(budget.transactions.is_empty());
+    }
+
+    #[test]
+    fn original_split() {
+        let mut budget = Budget::default();
+        set_budget(&mut budget, 1200).unwrap();
+
+        assert_eq!(budget.tools_budget, 200);
+        assert_eq!(budget.consumables_budget, 300);
+        assert_eq!(budget.weapons_budget, 700);
+    }
+
+    #[test]
+    fn custom_split() {
+        let mut budget = Budget {
+            split: Some(BudgetSplit {
+                weapons: 50,
+                tools: 25,
+                consumables: 25,
+            }),
+            ..Budget::default()
+        };
+        set_budget(&mut budget, 1000).unwrap();
+
+        assert_eq!(budget.tools_budget, 250);
+        assert_eq!(budget.consumables_budget, 250);
+        assert_eq!(budget.weapons_budget, 500);
+    }
+
+    #[test]
+    fn invalid_split() {
+        let split = BudgetSplit {
+            weapons: 50,
+            tools: 50,
+            consumables: 50,
+        };
+        let mut budget = Budget {
+            split: Some(split.clone()),
+            ..Budget::default()
+        };
+
+        assert_eq!(
+            set_budget(&mut budget, 1000),
+            Err(TransactionResult::ErrorBudgeting)
+        );
+
+        let (loadout, _) = random_loadout(&Config {
+            max_cost: Some(1000),
+            budget_split: Some(split),
+            ..Config::default()
+        });
+
+        assert!(loadout
+            .errors
+            .iter()
+            .any(|error| matches!(error, LoadoutError::Budget { .. })));
+        assert!(loadout.weapon_one.item.is_none());
+    }
+
+    #[test]
+    fn pool_ignores_split() {
+        let mut budget = Budget {
+            leftover_policy: LeftoverPolicy::Pool,
+            ..Budget::default()
+        };
+        set_budget(&mut budget, 1000).unwrap();
+        process_transaction(
+            &mut budget,
+            Transaction::purchase(TransactionCategory::Tool, 0, 900, "Tool".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(budget.weapons_budget, 100);
+        assert_eq!(budget.consumables_budget, 100);
+    }
+
+    #[test]
+    fn only_cascade_passes_leftovers_on() {
+        for (leftover_policy, tools_budget) in [
+            (LeftoverPolicy::Cascade, 900),
+            (LeftoverPolicy::Strict, 200),
+        ] {
+            let mut budget = Budget {
+                leftover_policy,
+                ..Budget::default()
+            };
+            set_budget(&mut budget, 1200).unwrap();
+            transfer_weapons_to_tools(&mut budget);
+
+            assert_eq!(budget.tools_budget, tools_budget);
         }
     }
 }
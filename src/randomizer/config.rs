@@ -1,6 +1,19 @@
-use crate::content::ToolSlotPreference;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use crate::content::{
+    generic_item::CustomAmmo, BulletSize, BulletVariant, ConsumableSlotPreference, GenericItem,
+    ToolSlotPreference, WeaponSlotPreference, CORE_SEARCH_UTIL,
+};
+use crate::randomizer::{
+    budget::{BudgetSplit, LeftoverPolicy},
+    limits::ItemLimit,
+    theme::{Theme, ThemeFallback},
+    unlocks::UnlockKey,
+    Unlocks,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum ToggleOption {
     DualWield,
     DuplicateWeapons,
@@ -10,17 +23,90 @@ pub enum ToggleOption {
     AlwaysDuplicateWeapons,
     AlwaysCustomAmmo,
     AlwaysQuartermaster,
+    OnlyUnlocked,
+    BestValue,
+}
+
+impl fmt::Display for ToggleOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::DualWield => "DualWield",
+                Self::DuplicateWeapons => "DuplicateWeapons",
+                Self::CustomAmmo => "CustomAmmo",
+                Self::Quartermaster => "Quartermaster",
+                Self::AlwaysDualWield => "AlwaysDualWield",
+                Self::AlwaysDuplicateWeapons => "AlwaysDuplicateWeapons",
+                Self::AlwaysCustomAmmo => "AlwaysCustomAmmo",
+                Self::AlwaysQuartermaster => "AlwaysQuartermaster",
+                Self::OnlyUnlocked => "OnlyUnlocked",
+                Self::BestValue => "BestValue",
+            }
+        )
+    }
+}
+
+impl TryFrom<String> for ToggleOption {
+    type Error = &'static str;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match &*val {
+            "DualWield" => Ok(Self::DualWield),
+            "DuplicateWeapons" => Ok(Self::DuplicateWeapons),
+            "CustomAmmo" => Ok(Self::CustomAmmo),
+            "Quartermaster" => Ok(Self::Quartermaster),
+            "AlwaysDualWield" => Ok(Self::AlwaysDualWield),
+            "AlwaysDuplicateWeapons" => Ok(Self::AlwaysDuplicateWeapons),
+            "AlwaysCustomAmmo" => Ok(Self::AlwaysCustomAmmo),
+            "AlwaysQuartermaster" => Ok(Self::AlwaysQuartermaster),
+            "OnlyUnlocked" => Ok(Self::OnlyUnlocked),
+            "BestValue" => Ok(Self::BestValue),
+            _ => Err("Invalid toggle option"),
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+// Fields missing from stored configs and presets are taken from `Config::default`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Config {
     pub toggled_options: Vec<ToggleOption>,
     pub max_rank: u8,
     pub max_cost: Option<u16>,
+    // Loadouts are regenerated a few times trying to spend at least this much, together with
+    // `max_cost` this is the range the total cost should land in.
+    pub min_cost: Option<u16>,
+    // `None` keeps the original split, see `Budget::split`.
+    pub budget_split: Option<BudgetSplit>,
+    pub leftover_policy: LeftoverPolicy,
+    // When `None` a new seed is picked for every generated loadout.
+    pub seed: Option<u64>,
     pub tool_preferences: [ToolSlotPreference; 4],
+    // When a preference can't be met by any weapon that fits the slot it's ignored.
+    pub weapon_preferences: [WeaponSlotPreference; 2],
+    pub consumable_preferences: [ConsumableSlotPreference; 4],
+    // Relative weights for picking the size of a weapon's primary ammo, they don't need to add up
+    // to 100. See `get_bullet_size_chance` for how weapons are picked with them.
     pub long_ammo_chance: f32,
     pub medium_ammo_chance: f32,
     pub compact_ammo_chance: f32,
+    // Weapons, ammo, tools and consumables fitting the theme are picked over anything else.
+    pub theme: Theme,
+    pub theme_fallback: ThemeFallback,
+    // Banned items are never picked, banned ammo is never picked for any weapon.
+    pub excluded_items: Vec<UnlockKey>,
+    pub excluded_ammo: Vec<BulletVariant>,
+    // Required items are put in every loadout before anything else, and so is one item from each
+    // of the groups.
+    pub required_items: Vec<UnlockKey>,
+    pub required_groups: Vec<Vec<UnlockKey>>,
+    // How few or how many tools and consumables of a kind can be picked, counted across both.
+    pub item_limits: Vec<ItemLimit>,
+    // Saved on its own under `storage::UNLOCKS_KEY` so it isn't lost when the config changes.
+    #[serde(skip)]
+    pub unlocks: Unlocks,
 }
 
 impl Default for Config {
@@ -33,14 +119,39 @@ impl Default for Config {
             ],
             max_rank: 100,
             max_cost: None,
+            min_cost: None,
+            budget_split: None,
+            leftover_policy: LeftoverPolicy::default(),
+            seed: None,
             tool_preferences: [ToolSlotPreference::NoPreference; 4],
+            weapon_preferences: [WeaponSlotPreference::NoPreference; 2],
+            consumable_preferences: [ConsumableSlotPreference::NoPreference; 4],
             long_ammo_chance: 33.33,
             medium_ammo_chance: 33.33,
             compact_ammo_chance: 33.33,
+            theme: Theme::default(),
+            theme_fallback: ThemeFallback::default(),
+            excluded_items: vec![],
+            excluded_ammo: vec![],
+            required_items: vec![],
+            required_groups: vec![],
+            item_limits: vec![],
+            unlocks: Unlocks::default(),
         }
     }
 }
 
+// The size group a weapon is weighted by, `None` for every size without its own chance.
+#[must_use]
+pub fn weapon_size_group(weapon: &GenericItem) -> Option<BulletSize> {
+    weapon.get_bullet_size().filter(|size| {
+        matches!(
+            size,
+            BulletSize::Long | BulletSize::Medium | BulletSize::Compact
+        )
+    })
+}
+
 impl Config {
     pub fn remove_option(&mut self, option: ToggleOption) {
         if let Some(option_pos) = self.toggled_options.iter().position(|x| x == &option) {
@@ -56,7 +167,129 @@ impl Config {
         }
     }
 
+    #[must_use]
     pub fn option_exists(&self, option: ToggleOption) -> bool {
         self.toggled_options.contains(&option)
     }
+
+    pub fn toggle_excluded_item(&mut self, key: UnlockKey) {
+        if let Some(pos) = self.excluded_items.iter().position(|x| x == &key) {
+            self.excluded_items.remove(pos);
+        } else {
+            self.excluded_items.push(key);
+        }
+    }
+
+    pub fn toggle_excluded_ammo(&mut self, ammo: BulletVariant) {
+        if let Some(pos) = self.excluded_ammo.iter().position(|x| x == &ammo) {
+            self.excluded_ammo.remove(pos);
+        } else {
+            self.excluded_ammo.push(ammo);
+        }
+    }
+
+    #[must_use]
+    pub fn item_allowed(&self, item: &GenericItem) -> bool {
+        CORE_SEARCH_UTIL.get_item_rank(item) <= self.max_rank
+            && !self.excluded_items.contains(&UnlockKey::from_item(item))
+            && (!self.option_exists(ToggleOption::OnlyUnlocked)
+                || self.unlocks.item_unlocked(item, self.max_rank))
+    }
+
+    #[must_use]
+    pub fn ammo_allowed(&self, weapon: &GenericItem, ammo: &CustomAmmo) -> bool {
+        // Default ammo has no variant and is always allowed.
+        ammo.1.iter().all(|variant| {
+            CORE_SEARCH_UTIL.get_bullet_rank(weapon, variant) <= self.max_rank
+                && !self.excluded_ammo.contains(variant)
+                && (!self.option_exists(ToggleOption::OnlyUnlocked)
+                    || self.unlocks.ammo_unlocked(weapon, variant, self.max_rank))
+        })
+    }
+
+    // Narrows the candidates for a slot down to the ones fitting the theme, when none of them do
+    // `theme_fallback` decides between all of them or none.
+    #[must_use]
+    pub fn theme_items<'a>(&self, items: Vec<&'a GenericItem>) -> Vec<&'a GenericItem> {
+        if self.theme == Theme::NoTheme {
+            return items;
+        }
+
+        let custom_ammo = self.option_exists(ToggleOption::CustomAmmo);
+        let themed = items
+            .iter()
+            .filter(|item| self.theme.item_matches(item, custom_ammo))
+            .copied()
+            .collect::<Vec<&GenericItem>>();
+
+        if themed.is_empty() && self.theme_fallback == ThemeFallback::AnyItem {
+            items
+        } else {
+            themed
+        }
+    }
+
+    // Same as `theme_items` for the ammo variants of a weapon.
+    #[must_use]
+    pub fn theme_ammo(&self, weapon: &GenericItem, ammo: Vec<CustomAmmo>) -> Vec<CustomAmmo> {
+        if self.theme == Theme::NoTheme {
+            return ammo;
+        }
+
+        let themed = ammo
+            .iter()
+            .filter(|ammo_type| self.theme.ammo_matches(weapon, ammo_type))
+            .cloned()
+            .collect::<Vec<CustomAmmo>>();
+
+        // Default ammo that already fits doesn't need replacing with something that doesn't.
+        if themed.is_empty()
+            && self.theme_fallback == ThemeFallback::AnyItem
+            && !self.theme.default_ammo_matches(weapon)
+        {
+            ammo
+        } else {
+            themed
+        }
+    }
+
+    // A weapon that only fits the theme through an ammo variant has to be given that ammo.
+    #[must_use]
+    pub fn theme_needs_ammo(&self, weapon: &GenericItem, ammo: &[CustomAmmo]) -> bool {
+        self.theme != Theme::NoTheme
+            && !self.theme.default_ammo_matches(weapon)
+            && ammo
+                .iter()
+                .any(|ammo_type| self.theme.ammo_matches(weapon, ammo_type))
+    }
+
+    // The weight of a size group when picking a weapon. A size is picked first, out of the sizes
+    // with at least one weapon that can be picked, then a weapon of that size is picked evenly, so
+    // the chances don't depend on how many weapons use each size. Weapons using any other size
+    // (Shell, Special, Derringer, Flare, or melee-only weapons with no ammo, all passed in as
+    // `None`) share one group weighted by the average of the three chances, so they stay as likely
+    // as an average size no matter which size is favoured. A group with a chance of 0 is never
+    // picked, with all three at 0 that includes the other sizes and no weapon is picked.
+    #[must_use]
+    pub fn get_bullet_size_chance(&self, bullet_size: Option<&BulletSize>) -> f32 {
+        let chance = match bullet_size {
+            Some(BulletSize::Long) => self.long_ammo_chance,
+            Some(BulletSize::Medium) => self.medium_ammo_chance,
+            Some(BulletSize::Compact) => self.compact_ammo_chance,
+            _ => (self.long_ammo_chance + self.medium_ammo_chance + self.compact_ammo_chance) / 3.0,
+        };
+
+        // Negative or NaN chances from bad input are treated as never picking that size.
+        if chance > 0.0 {
+            chance
+        } else {
+            0.0
+        }
+    }
+
+    // The chance of the size group `weapon` is in, see `get_bullet_size_chance`.
+    #[must_use]
+    pub fn get_weapon_chance(&self, weapon: &GenericItem) -> f32 {
+        self.get_bullet_size_chance(weapon_size_group(weapon).as_ref())
+    }
 }
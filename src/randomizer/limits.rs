@@ -0,0 +1,294 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::content::{GenericItem, ToolSlotPreference, UsageType, UtilityType, CORE_SEARCH_UTIL};
+use crate::randomizer::{loadout::LoadoutError, Config, Loadout};
+
+// What a limit counts, tool categories only count tools while utility types count tools and
+// consumables alike.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "t", content = "c", rename_all = "snake_case")]
+pub enum LimitCategory {
+    Tool(ToolSlotPreference),
+    Utility(UtilityType),
+}
+
+impl fmt::Display for LimitCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tool(ToolSlotPreference::NoPreference) => write!(f, "Tools"),
+            Self::Tool(preference) => write!(f, "{} Tools", preference.to_string()),
+            Self::Utility(utility_type) => write!(f, "{utility_type:?} Items"),
+        }
+    }
+}
+
+impl LimitCategory {
+    // Every consumable can be used as a club, so melee types aren't counted for utility types.
+    #[must_use]
+    pub fn matches(&self, item: &GenericItem) -> bool {
+        match self {
+            Self::Tool(preference) => CORE_SEARCH_UTIL
+                .get_tools_by_preference(*preference)
+                .contains(&item),
+            Self::Utility(utility_type) => {
+                item.usage_types.iter().any(|usage_type| match usage_type {
+                    UsageType::Use { types, .. }
+                    | UsageType::Throw { types, .. }
+                    | UsageType::ThrowLight { types, .. }
+                    | UsageType::Placeable { types, .. } => types.contains(utility_type),
+                    _ => false,
+                })
+            }
+        }
+    }
+}
+
+// Categories offered in the advanced options.
+#[must_use]
+pub fn limit_categories() -> Vec<LimitCategory> {
+    vec![
+        LimitCategory::Tool(ToolSlotPreference::Medkit),
+        LimitCategory::Tool(ToolSlotPreference::Melee),
+        LimitCategory::Tool(ToolSlotPreference::Throwable),
+        LimitCategory::Tool(ToolSlotPreference::Tripmines),
+        LimitCategory::Tool(ToolSlotPreference::Decoys),
+        LimitCategory::Tool(ToolSlotPreference::Others),
+        LimitCategory::Utility(UtilityType::Heal),
+        LimitCategory::Utility(UtilityType::Explosion),
+        LimitCategory::Utility(UtilityType::Fire),
+        LimitCategory::Utility(UtilityType::Poison),
+        LimitCategory::Utility(UtilityType::Choke),
+        LimitCategory::Utility(UtilityType::Antidote),
+        LimitCategory::Utility(UtilityType::Stamina),
+        LimitCategory::Utility(UtilityType::Light),
+    ]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ItemLimit {
+    pub category: LimitCategory,
+    // Zero is no minimum.
+    pub min: u8,
+    pub max: Option<u8>,
+}
+
+impl Default for ItemLimit {
+    fn default() -> Self {
+        Self {
+            category: LimitCategory::Tool(ToolSlotPreference::Medkit),
+            min: 0,
+            max: None,
+        }
+    }
+}
+
+impl ItemLimit {
+    fn count(&self, loadout: &Loadout) -> usize {
+        loadout
+            .tools
+            .iter()
+            .chain(&loadout.consumables)
+            .filter_map(|lockable| lockable.item.as_ref())
+            .filter(|item| self.category.matches(item))
+            .count()
+    }
+}
+
+// Room left under each maximum, worked out once so searching combinations of items stays quick.
+pub struct MaxLimits {
+    limits: Vec<(Vec<&'static GenericItem>, usize)>,
+}
+
+impl MaxLimits {
+    #[must_use]
+    pub fn new(config: &Config, loadout: &Loadout) -> Self {
+        let limits = config
+            .item_limits
+            .iter()
+            .filter_map(|limit| {
+                let max = usize::from(limit.max?);
+                let items = CORE_SEARCH_UTIL
+                    .tools
+                    .iter()
+                    .chain(&CORE_SEARCH_UTIL.consumables)
+                    .filter(|item| limit.category.matches(item))
+                    .collect::<Vec<&GenericItem>>();
+
+                Some((items, max.saturating_sub(limit.count(loadout))))
+            })
+            .collect();
+
+        Self { limits }
+    }
+
+    // Positions of the maximums `item` counts towards.
+    #[must_use]
+    pub fn matching(&self, item: &GenericItem) -> Vec<usize> {
+        self.limits
+            .iter()
+            .enumerate()
+            .filter(|(_, (items, _))| items.contains(&item))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    // How many more items each maximum allows, in the same order as `matching`.
+    #[must_use]
+    pub fn rooms(&self) -> Vec<usize> {
+        self.limits.iter().map(|(_, room)| *room).collect()
+    }
+}
+
+// Drops any candidate that would go over a maximum given what's already in the loadout.
+#[must_use]
+pub fn within_max<'a>(
+    config: &Config,
+    loadout: &Loadout,
+    candidates: Vec<&'a GenericItem>,
+) -> Vec<&'a GenericItem> {
+    if config.item_limits.is_empty() {
+        return candidates;
+    }
+
+    let max_limits = MaxLimits::new(config, loadout);
+    let rooms = max_limits.rooms();
+
+    candidates
+        .into_iter()
+        .filter(|item| max_limits.matching(item).iter().all(|pos| rooms[*pos] > 0))
+        .collect()
+}
+
+// The candidates that go towards the first minimum not met yet, empty when every minimum is met
+// or none of the candidates help.
+#[must_use]
+pub fn needed<'a>(
+    config: &Config,
+    loadout: &Loadout,
+    candidates: &[&'a GenericItem],
+) -> Vec<&'a GenericItem> {
+    config
+        .item_limits
+        .iter()
+        .filter(|limit| limit.count(loadout) < usize::from(limit.min))
+        .map(|limit| {
+            candidates
+                .iter()
+                .filter(|item| limit.category.matches(item))
+                .copied()
+                .collect::<Vec<&GenericItem>>()
+        })
+        .find(|needed| !needed.is_empty())
+        .unwrap_or_default()
+}
+
+// Narrows slots down to the items that meet a minimum, one slot for each item still needed. Used
+// when every slot is searched at once instead of picked one at a time.
+pub fn reserve_needed(config: &Config, loadout: &Loadout, slots: &mut [Vec<&GenericItem>]) {
+    let mut reserved = vec![false; slots.len()];
+
+    for limit in &config.item_limits {
+        let mut needed = usize::from(limit.min).saturating_sub(limit.count(loadout));
+
+        for (options, reserved) in slots.iter_mut().zip(reserved.iter_mut()) {
+            if needed == 0 {
+                break;
+            }
+
+            if *reserved || !options.iter().any(|item| limit.category.matches(item)) {
+                continue;
+            }
+
+            options.retain(|item| limit.category.matches(item));
+            *reserved = true;
+            needed -= 1;
+        }
+    }
+}
+
+// Limits that couldn't be kept, either from locked items or nothing fitting the budget.
+pub fn check_limits(loadout: &mut Loadout, config: &Config) {
+    for limit in &config.item_limits {
+        let count = limit.count(loadout);
+
+        if count < usize::from(limit.min) {
+            loadout.errors.push(LoadoutError::Rule {
+                error: format!(
+                    "Unable to fit at least {} {}, only found {count}.",
+                    limit.min, limit.category
+                ),
+            });
+        }
+
+        if let Some(max) = limit.max.filter(|max| count > usize::from(*max)) {
+            loadout.errors.push(LoadoutError::Rule {
+                error: format!("There are {count} {}, the limit is {max}.", limit.category),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::test_util::random_loadout;
+
+    #[test]
+    fn limits_are_kept() {
+        let medkits = ItemLimit {
+            category: LimitCategory::Tool(ToolSlotPreference::Medkit),
+            min: 1,
+            max: Some(1),
+        };
+        let explosions = ItemLimit {
+            category: LimitCategory::Utility(UtilityType::Explosion),
+            min: 0,
+            max: Some(0),
+        };
+        let heals = ItemLimit {
+            category: LimitCategory::Utility(UtilityType::Heal),
+            min: 2,
+            max: None,
+        };
+
+        let (loadout, _) = random_loadout(&Config {
+            seed: Some(0),
+            item_limits: vec![medkits.clone(), explosions.clone(), heals.clone()],
+            ..Config::default()
+        });
+
+        assert_eq!(medkits.count(&loadout), 1);
+        assert_eq!(explosions.count(&loadout), 0);
+        assert!(heals.count(&loadout) >= 2);
+        assert!(loadout.errors.is_empty(), "{:?}", loadout.errors);
+    }
+
+    #[test]
+    fn locked_items_break_limits() {
+        let mut loadout = Loadout::default();
+        loadout.tools[0].item = CORE_SEARCH_UTIL
+            .get_tools_by_preference(ToolSlotPreference::Medkit)
+            .first()
+            .map(|tool| (*tool).clone());
+
+        let config = Config {
+            item_limits: vec![
+                ItemLimit {
+                    category: LimitCategory::Tool(ToolSlotPreference::Medkit),
+                    min: 0,
+                    max: Some(0),
+                },
+                ItemLimit {
+                    category: LimitCategory::Tool(ToolSlotPreference::Melee),
+                    min: 1,
+                    max: None,
+                },
+            ],
+            ..Config::default()
+        };
+        check_limits(&mut loadout, &config);
+
+        assert_eq!(loadout.errors.len(), 2);
+    }
+}
@@ -1,31 +1,178 @@
-use rand::{rngs::ThreadRng, thread_rng, Rng};
-use std::cmp::Ordering;
+use rand::{distributions::WeightedIndex, rngs::StdRng, thread_rng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, fmt};
 
 use crate::content::{
     generic_item::{CustomAmmo, GenericItemLockable},
-    BulletSize, GenericItem, Slot, ToolSlotPreference, CORE_SEARCH_UTIL,
+    BulletSize, ConsumableSlotPreference, GenericItem, Slot, ToolSlotPreference,
+    WeaponSlotPreference, CORE_SEARCH_UTIL,
+};
+use crate::randomizer::budget::{Transaction, TransactionCategory, TransactionResult};
+use crate::randomizer::{
+    best_value, budget,
+    config::{weapon_size_group, ToggleOption},
+    limits, required,
+    theme::Theme,
+    Budget, Config, LoadoutInvalid,
 };
-use crate::randomizer::budget::{Transaction, TransactionResult};
-use crate::randomizer::{budget, config::ToggleOption, Budget, Config, LoadoutInvalid};
 
-const ERR_INSF_FND_LOCK: &str =
+pub const ERR_INSF_FND_LOCK: &str =
     "Insufficient Funds, try unlocking this item or increase your budget.";
+pub const ERR_INSF_FND: &str = "Insufficient Funds, couldn't afford an item for this slot.";
+pub const ERR_INSF_FND_AMMO: &str =
+    "Insufficient Funds, couldn't afford this ammo so it was left as default ammo.";
+const ERR_BUDGETING: &str =
+    "Couldn't budget for this item, make sure the budget split adds to 100%.";
 const ERR_SLOT_GT_UNSIGNED: &str = "Somehow got slot greater than u8.";
 const MAX_DUPE_CHECK_AMOUNT: usize = 10;
+// How many loadouts are generated looking for one that spends at least the minimum cost before
+// settling for the most expensive one.
+const MAX_SPEND_ATTEMPTS: usize = 25;
 pub const INVALID_DUALWIELD_NAMES: &[&str] =
     &["Cavalry Saber", "Hand Crossbow", "Combat Axe", "Machete"];
 
 #[allow(clippy::module_name_repetitions)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum LoadoutError {
-    Weapon { error: String, slot: u8 },
-    Tool { error: String, slot: u8 },
-    Consumable { error: String, slot: u8 },
+    // Shortfall is how much more money was needed for the purchase, 0 if it failed for another
+    // reason.
+    Weapon {
+        error: String,
+        slot: u8,
+        shortfall: u16,
+    },
+    // Ammo slot is the position in the weapons equipped ammo.
+    Ammo {
+        error: String,
+        slot: u8,
+        ammo_slot: u8,
+        shortfall: u16,
+    },
+    Tool {
+        error: String,
+        slot: u8,
+        shortfall: u16,
+    },
+    Consumable {
+        error: String,
+        slot: u8,
+        shortfall: u16,
+    },
+    Budget {
+        error: String,
+    },
+    // A rule that couldn't be met, such as one of the squad options.
+    Rule {
+        error: String,
+    },
 }
 
-#[derive(Debug, Clone)]
+impl LoadoutError {
+    // Ammo transactions use `ammo_slot`, every other category ignores it.
+    #[must_use]
+    pub fn from_transaction(
+        error: &str,
+        category: TransactionCategory,
+        slot: u8,
+        ammo_slot: u8,
+        result: TransactionResult,
+    ) -> Self {
+        let (error, shortfall) = match result {
+            TransactionResult::InsufficientFunds(shortfall) => (error.to_string(), shortfall),
+            TransactionResult::ErrorBudgeting => (ERR_BUDGETING.to_string(), 0),
+        };
+
+        match category {
+            TransactionCategory::Weapon => Self::Weapon {
+                error,
+                slot,
+                shortfall,
+            },
+            TransactionCategory::Bullet => Self::Ammo {
+                error,
+                slot,
+                ammo_slot,
+                shortfall,
+            },
+            TransactionCategory::Tool => Self::Tool {
+                error,
+                slot,
+                shortfall,
+            },
+            TransactionCategory::Consumable => Self::Consumable {
+                error,
+                slot,
+                shortfall,
+            },
+        }
+    }
+
+    // The slot `is_for_slot` checks against.
+    pub const fn slot_mut(&mut self, category: TransactionCategory) -> Option<&mut u8> {
+        match (self, category) {
+            (Self::Weapon { slot, .. } | Self::Ammo { slot, .. }, TransactionCategory::Weapon)
+            | (Self::Ammo { slot, .. }, TransactionCategory::Bullet)
+            | (Self::Tool { slot, .. }, TransactionCategory::Tool)
+            | (Self::Consumable { slot, .. }, TransactionCategory::Consumable) => Some(slot),
+            _ => None,
+        }
+    }
+
+    // Ammo errors belong to the weapon they're equipped on as well.
+    #[must_use]
+    pub const fn is_for_slot(&self, category: TransactionCategory, check_slot: u8) -> bool {
+        match (self, category) {
+            (Self::Weapon { slot, .. } | Self::Ammo { slot, .. }, TransactionCategory::Weapon)
+            | (Self::Ammo { slot, .. }, TransactionCategory::Bullet)
+            | (Self::Tool { slot, .. }, TransactionCategory::Tool)
+            | (Self::Consumable { slot, .. }, TransactionCategory::Consumable) => {
+                *slot == check_slot
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for LoadoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Weapon {
+                error, shortfall, ..
+            }
+            | Self::Tool {
+                error, shortfall, ..
+            }
+            | Self::Consumable {
+                error, shortfall, ..
+            } if *shortfall > 0 => write!(f, "{error} Short by {shortfall}."),
+            Self::Ammo {
+                error,
+                ammo_slot,
+                shortfall,
+                ..
+            } if *shortfall > 0 => {
+                write!(
+                    f,
+                    "Ammo slot {}: {error} Short by {shortfall}.",
+                    ammo_slot + 1
+                )
+            }
+            Self::Ammo {
+                error, ammo_slot, ..
+            } => write!(f, "Ammo slot {}: {error}", ammo_slot + 1),
+            Self::Weapon { error, .. }
+            | Self::Tool { error, .. }
+            | Self::Consumable { error, .. }
+            | Self::Budget { error }
+            | Self::Rule { error } => write!(f, "{error}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Loadout {
     pub errors: Vec<LoadoutError>,
+    pub seed: Option<u64>,
     pub weapon_one: GenericItemLockable,
     pub weapon_two: GenericItemLockable,
     pub tools: [GenericItemLockable; 4],
@@ -36,6 +183,7 @@ impl Default for Loadout {
     fn default() -> Self {
         Self {
             errors: vec![],
+            seed: None,
             weapon_one: GenericItemLockable {
                 item: None,
                 locked: false,
@@ -60,14 +208,27 @@ impl Default for Loadout {
     }
 }
 
+impl Loadout {
+    #[must_use]
+    pub fn get_slot_errors(&self, category: TransactionCategory, slot: u8) -> Vec<LoadoutError> {
+        self.errors
+            .iter()
+            .filter(|error| error.is_for_slot(category, slot))
+            .cloned()
+            .collect()
+    }
+}
+
 fn transaction_from_custom_ammo(
     budget: &mut Budget,
+    slot: u8,
     ammo: &CustomAmmo,
 ) -> Result<(), TransactionResult> {
     budget::process_transaction(
         budget,
-        Transaction::Bullet(
-            false,
+        Transaction::purchase(
+            TransactionCategory::Bullet,
+            slot,
             ammo.2,
             ammo.1
                 .as_ref()
@@ -76,17 +237,53 @@ fn transaction_from_custom_ammo(
     )
 }
 
+// Same as `transaction_from_custom_ammo` but failures are recorded as errors.
+fn purchase_custom_ammo(
+    budget: &mut Budget,
+    errors: &mut Vec<LoadoutError>,
+    slot: u8,
+    ammo_slot: u8,
+    ammo: &CustomAmmo,
+) -> bool {
+    match transaction_from_custom_ammo(budget, slot, ammo) {
+        Ok(()) => true,
+        Err(e) => {
+            errors.push(LoadoutError::from_transaction(
+                ERR_INSF_FND_AMMO,
+                TransactionCategory::Bullet,
+                slot,
+                ammo_slot,
+                e,
+            ));
+            false
+        }
+    }
+}
+
 fn transaction_from_weapon(
     budget: &mut Budget,
+    slot: u8,
     weapon: &GenericItem,
     refund: bool,
 ) -> Result<(), TransactionResult> {
+    let transaction = if refund {
+        Transaction::refund
+    } else {
+        Transaction::purchase
+    };
+
     budget::process_transaction(
         budget,
-        Transaction::Weapon(refund, weapon.get_cost(), weapon.to_full_name()),
+        transaction(
+            TransactionCategory::Weapon,
+            slot,
+            weapon.get_cost(),
+            weapon.to_full_name(),
+        ),
     )
 }
 
+#[must_use]
 pub fn get_valid_slots(quartermaster: bool, slot: &Slot) -> Vec<Slot> {
     if quartermaster {
         match slot {
@@ -105,13 +302,15 @@ pub fn get_valid_slots(quartermaster: bool, slot: &Slot) -> Vec<Slot> {
 pub fn item_lte_cost<'a>(
     items: &'a [&GenericItem],
     cost: u16,
-    rng: &mut ThreadRng,
+    config: &Config,
+    rng: &mut StdRng,
 ) -> Option<GenericItem> {
     let items = items
         .iter()
-        .filter(|item| item.get_cost() <= cost)
+        .filter(|item| item.get_cost() <= cost && config.item_allowed(item))
         .copied()
         .collect::<Vec<&GenericItem>>();
+    let items = config.theme_items(items);
 
     if items.is_empty() {
         None
@@ -120,14 +319,102 @@ pub fn item_lte_cost<'a>(
     }
 }
 
-pub fn refund_item(budget: &mut Budget, item: &GenericItemLockable) {
+// Same as `item_lte_cost` but weapons are weighted by the ammo chances in the config.
+pub fn weapon_lte_cost(
+    weapons: &[&GenericItem],
+    cost: u16,
+    config: &Config,
+    rng: &mut StdRng,
+) -> Option<GenericItem> {
+    let weapons = weapons
+        .iter()
+        .filter(|weapon| weapon.get_cost() <= cost && config.item_allowed(weapon))
+        .copied()
+        .collect::<Vec<&GenericItem>>();
+    let weapons = config.theme_items(weapons);
+
+    if weapons.is_empty() {
+        return None;
+    }
+
+    // Long, medium and compact weapons are grouped by size, everything else is grouped together.
+    let mut groups: Vec<(Option<BulletSize>, Vec<&GenericItem>)> = vec![];
+
+    for weapon in &weapons {
+        let size = weapon_size_group(weapon);
+
+        match groups
+            .iter_mut()
+            .find(|(group_size, _)| *group_size == size)
+        {
+            Some((_, group)) => group.push(weapon),
+            None => groups.push((size, vec![weapon])),
+        }
+    }
+
+    // A chance of 0 is never picked, so there's nothing to pick when every group left has one.
+    let weights = groups
+        .iter()
+        .map(|(size, _)| config.get_bullet_size_chance(size.as_ref()));
+    let index = WeightedIndex::new(weights).ok()?;
+    let group = &groups[rng.sample(index)].1;
+
+    Some(group[rng.gen_range(0..group.len())].clone())
+}
+
+// Whether `weapon` fits the preference of its slot, compared by name so dual wielded weapons and
+// weapons with ammo picked still match.
+#[must_use]
+pub fn weapon_preferred(config: &Config, slot: u8, weapon: &GenericItem) -> bool {
+    CORE_SEARCH_UTIL
+        .get_weapons_by_preference(
+            config
+                .weapon_preferences
+                .get(usize::from(slot))
+                .copied()
+                .unwrap_or(WeaponSlotPreference::NoPreference),
+        )
+        .iter()
+        .any(|preferred| preferred.name == weapon.name && preferred.variant == weapon.variant)
+}
+
+// Picks a weapon fitting the slot preference, if none of them are left once the cost, rank and
+// theme are checked the preference is ignored.
+fn preferred_weapon_lte_cost(
+    weapons: &[&GenericItem],
+    slot: u8,
+    cost: u16,
+    config: &Config,
+    rng: &mut StdRng,
+) -> Option<GenericItem> {
+    let preferred = weapons
+        .iter()
+        .filter(|weapon| weapon_preferred(config, slot, weapon))
+        .copied()
+        .collect::<Vec<&GenericItem>>();
+
+    weapon_lte_cost(&preferred, cost, config, rng)
+        .or_else(|| weapon_lte_cost(weapons, cost, config, rng))
+}
+
+// Refunding an item also clears any errors from failing to purchase it.
+pub fn refund_item(
+    budget: &mut Budget,
+    errors: &mut Vec<LoadoutError>,
+    category: TransactionCategory,
+    slot: u8,
+    item: &GenericItemLockable,
+) {
+    errors.retain(|error| !error.is_for_slot(category, slot));
+
     if let Some(item) = &item.item {
         for ammo_type in &item.ammo_equipped {
             if ammo_type.2 > 0 {
                 let _tx = budget::process_transaction(
                     budget,
-                    Transaction::Bullet(
-                        true,
+                    Transaction::refund(
+                        TransactionCategory::Bullet,
+                        slot,
                         ammo_type.2,
                         ammo_type
                             .1
@@ -141,20 +428,30 @@ pub fn refund_item(budget: &mut Budget, item: &GenericItemLockable) {
 
         let _tx = budget::process_transaction(
             budget,
-            Transaction::Weapon(true, item.get_cost(), item.to_full_name()),
+            Transaction::refund(category, slot, item.get_cost(), item.to_full_name()),
         )
         .ok();
     }
 }
 
-pub fn purchase_item(budget: &mut Budget, item: &GenericItemLockable) {
+// Failed purchases still keep the item, the errors report how much is missing for it.
+pub fn purchase_item(
+    budget: &mut Budget,
+    errors: &mut Vec<LoadoutError>,
+    category: TransactionCategory,
+    slot: u8,
+    item: &GenericItemLockable,
+) {
+    errors.retain(|error| !error.is_for_slot(category, slot));
+
     if let Some(item) = &item.item {
-        for ammo_type in &item.ammo_equipped {
+        for (ammo_slot, ammo_type) in (0..).zip(&item.ammo_equipped) {
             if ammo_type.2 > 0 {
                 let tx_res = budget::process_transaction(
                     budget,
-                    Transaction::Bullet(
-                        false,
+                    Transaction::purchase(
+                        TransactionCategory::Bullet,
+                        slot,
                         ammo_type.2,
                         ammo_type
                             .1
@@ -163,36 +460,95 @@ pub fn purchase_item(budget: &mut Budget, item: &GenericItemLockable) {
                     ),
                 );
 
-                if let Err(_e) = tx_res {
-                    // Come up with way to handle error.
+                if let Err(e) = tx_res {
+                    errors.push(LoadoutError::from_transaction(
+                        ERR_INSF_FND_LOCK,
+                        TransactionCategory::Bullet,
+                        slot,
+                        ammo_slot,
+                        e,
+                    ));
                 }
             }
         }
 
         let tx_res = budget::process_transaction(
             budget,
-            Transaction::Weapon(false, item.get_cost(), item.to_full_name()),
+            Transaction::purchase(category, slot, item.get_cost(), item.to_full_name()),
         );
 
-        if let Err(_e) = tx_res {
-            // Come up with way to handle error.
+        if let Err(e) = tx_res {
+            errors.push(LoadoutError::from_transaction(
+                ERR_INSF_FND_LOCK,
+                category,
+                slot,
+                0,
+                e,
+            ));
         }
     }
 }
 
+// Charges a loadout built outside of the randomizer against the budget `config` generates with,
+// leftovers move between categories the same way. Anything it can't afford is a slot error.
+pub fn price_loadout(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
+    *budget = Budget::default();
+
+    if let Some(max_cost) = config.max_cost {
+        budget.split.clone_from(&config.budget_split);
+        budget.leftover_policy = config.leftover_policy;
+
+        // The costs are still shown, just without a budget to check them against.
+        if budget::set_budget(budget, max_cost).is_err() {
+            loadout.errors.push(LoadoutError::Budget {
+                error: ERR_BUDGETING.to_string(),
+            });
+            *budget = Budget::default();
+        }
+    }
+
+    for (slot, weapon) in [(0, &loadout.weapon_one), (1, &loadout.weapon_two)] {
+        purchase_item(
+            budget,
+            &mut loadout.errors,
+            TransactionCategory::Weapon,
+            slot,
+            weapon,
+        );
+    }
+
+    budget::transfer_weapons_to_tools(budget);
+
+    for (slot, tool) in (0..).zip(&loadout.tools) {
+        purchase_item(
+            budget,
+            &mut loadout.errors,
+            TransactionCategory::Tool,
+            slot,
+            tool,
+        );
+    }
+
+    budget::transfer_tools_to_consumables(budget);
+
+    for (slot, consumable) in (0..).zip(&loadout.consumables) {
+        purchase_item(
+            budget,
+            &mut loadout.errors,
+            TransactionCategory::Consumable,
+            slot,
+            consumable,
+        );
+    }
+}
+
 pub fn set_default_ammo(item: &mut GenericItemLockable) -> bool {
     if let Some(item) = &mut item.item {
         if !item.ammo_equipped.is_empty() {
             return false;
         }
 
-        if let Some(bullet_size) = item.get_bullet_size() {
-            if item.additional_ammo_slots.unwrap_or(false) {
-                item.ammo_equipped = vec![(bullet_size.clone(), None, 0), (bullet_size, None, 0)];
-            } else {
-                item.ammo_equipped = vec![(bullet_size, None, 0)];
-            }
-        }
+        fill_default_ammo(item);
     }
 
     true
@@ -201,25 +557,36 @@ pub fn set_default_ammo(item: &mut GenericItemLockable) -> bool {
 pub fn initial_weapon(
     loadout: &mut Loadout,
     budget: &mut Budget,
-    rng: &mut ThreadRng,
-    quartermaster: bool,
+    config: &Config,
+    rng: &mut StdRng,
+    slot: u8,
     weapon: &mut GenericItemLockable,
     check: &GenericItemLockable,
 ) {
+    let quartermaster = config.option_exists(ToggleOption::Quartermaster);
+
     // Rusts powerful matching option makes advanced generation nice and easy!
     match (&mut weapon.item, weapon.locked) {
         // If weapon is locked and exists, process the weapon transaction.
         (Some(weapon), true) => {
             let tx_res = budget::process_transaction(
                 budget,
-                Transaction::Weapon(false, weapon.get_cost(), weapon.to_full_name()),
+                Transaction::purchase(
+                    TransactionCategory::Weapon,
+                    slot,
+                    weapon.get_cost(),
+                    weapon.to_full_name(),
+                ),
             );
 
-            if let Err(_e) = tx_res {
-                loadout.errors.push(LoadoutError::Weapon {
-                    error: ERR_INSF_FND_LOCK.to_string(),
-                    slot: 0,
-                });
+            if let Err(e) = tx_res {
+                loadout.errors.push(LoadoutError::from_transaction(
+                    ERR_INSF_FND_LOCK,
+                    TransactionCategory::Weapon,
+                    slot,
+                    0,
+                    e,
+                ));
             }
         }
         // The weapon one exists but isn't locked, or weapon one doesn't exist and isn't
@@ -228,23 +595,31 @@ pub fn initial_weapon(
             // If weapon two exists and is locked we want a weapon based on that, otherwise
             // generate a random weapon.
             if let (Some(check_weapon), _) = (&check.item, check.locked) {
-                let slot = check_weapon.get_slot();
-                let valid_slots = get_valid_slots(quartermaster, &slot);
+                let valid_slots = get_valid_slots(quartermaster, &check_weapon.get_slot());
                 let weapons = CORE_SEARCH_UTIL.get_weapons_by_sizes(&valid_slots);
-                let mut new_weapon = item_lte_cost(&weapons, budget.weapons_budget, rng);
+                let mut new_weapon =
+                    preferred_weapon_lte_cost(&weapons, slot, budget.weapons_budget, config, rng);
 
                 if let Some(new_check_weapon) = &new_weapon {
                     let tx_res = budget::process_transaction(
                         budget,
-                        Transaction::Weapon(
-                            false,
+                        Transaction::purchase(
+                            TransactionCategory::Weapon,
+                            slot,
                             new_check_weapon.get_cost(),
                             new_check_weapon.to_full_name(),
                         ),
                     );
 
-                    if let Err(_e) = tx_res {
+                    if let Err(e) = tx_res {
                         // We cannot afford the new weapon.
+                        loadout.errors.push(LoadoutError::from_transaction(
+                            ERR_INSF_FND,
+                            TransactionCategory::Weapon,
+                            slot,
+                            0,
+                            e,
+                        ));
                         new_weapon = None;
                     }
                 }
@@ -256,20 +631,29 @@ pub fn initial_weapon(
                     .iter()
                     .collect::<Vec<&GenericItem>>();
 
-                let new_weapon = item_lte_cost(&weapons, budget.weapons_budget, rng);
+                let new_weapon =
+                    preferred_weapon_lte_cost(&weapons, slot, budget.weapons_budget, config, rng);
 
                 weapon.item = if let Some(new_check_weapon) = &new_weapon {
                     let tx_res = budget::process_transaction(
                         budget,
-                        Transaction::Weapon(
-                            false,
+                        Transaction::purchase(
+                            TransactionCategory::Weapon,
+                            slot,
                             new_check_weapon.get_cost(),
                             new_check_weapon.to_full_name(),
                         ),
                     );
 
-                    if let Err(_e) = tx_res {
+                    if let Err(e) = tx_res {
                         // We cannot afford the new weapon.
+                        loadout.errors.push(LoadoutError::from_transaction(
+                            ERR_INSF_FND,
+                            TransactionCategory::Weapon,
+                            slot,
+                            0,
+                            e,
+                        ));
                         None
                     } else {
                         new_weapon
@@ -284,6 +668,11 @@ pub fn initial_weapon(
     }
 }
 
+/// Empties the unlocked tool slots and pays for the locked ones again.
+///
+/// # Panics
+///
+/// Never in practice, there are only four tool slots.
 pub fn reset_tools(loadout: &mut Loadout, budget: &mut Budget) {
     for (slot, tool) in loadout.tools.iter_mut().enumerate() {
         if !tool.locked {
@@ -291,21 +680,34 @@ pub fn reset_tools(loadout: &mut Loadout, budget: &mut Budget) {
         } else if let Some(tool) = &tool.item {
             let tx_res = budget::process_transaction(
                 budget,
-                Transaction::Tool(false, tool.get_cost(), tool.to_full_name()),
+                Transaction::purchase(
+                    TransactionCategory::Tool,
+                    slot.try_into().expect(ERR_SLOT_GT_UNSIGNED),
+                    tool.get_cost(),
+                    tool.to_full_name(),
+                ),
             );
 
             // If we can afford the transaction, purchase it, otherwise don't and report we
             // can't afford the item.
-            if let Err(_e) = tx_res {
-                loadout.errors.push(LoadoutError::Tool {
-                    error: ERR_INSF_FND_LOCK.to_string(),
-                    slot: slot.try_into().expect(ERR_SLOT_GT_UNSIGNED),
-                });
+            if let Err(e) = tx_res {
+                loadout.errors.push(LoadoutError::from_transaction(
+                    ERR_INSF_FND_LOCK,
+                    TransactionCategory::Tool,
+                    slot.try_into().expect(ERR_SLOT_GT_UNSIGNED),
+                    0,
+                    e,
+                ));
             }
         }
     }
 }
 
+/// Empties the unlocked consumable slots and pays for the locked ones again.
+///
+/// # Panics
+///
+/// Never in practice, there are only four consumable slots.
 pub fn reset_consumables(loadout: &mut Loadout, budget: &mut Budget) {
     for (slot, consumable) in loadout.consumables.iter_mut().enumerate() {
         if !consumable.locked {
@@ -313,20 +715,33 @@ pub fn reset_consumables(loadout: &mut Loadout, budget: &mut Budget) {
         } else if let Some(consumable) = &consumable.item {
             let tx_res = budget::process_transaction(
                 budget,
-                Transaction::Consumable(false, consumable.get_cost(), consumable.to_full_name()),
+                Transaction::purchase(
+                    TransactionCategory::Consumable,
+                    slot.try_into().expect(ERR_SLOT_GT_UNSIGNED),
+                    consumable.get_cost(),
+                    consumable.to_full_name(),
+                ),
             );
 
-            if let Err(_e) = tx_res {
-                loadout.errors.push(LoadoutError::Consumable {
-                    error: ERR_INSF_FND_LOCK.to_string(),
-                    slot: slot.try_into().expect(ERR_SLOT_GT_UNSIGNED),
-                });
+            if let Err(e) = tx_res {
+                loadout.errors.push(LoadoutError::from_transaction(
+                    ERR_INSF_FND_LOCK,
+                    TransactionCategory::Consumable,
+                    slot.try_into().expect(ERR_SLOT_GT_UNSIGNED),
+                    0,
+                    e,
+                ));
             }
         }
     }
 }
 
-pub fn always_quartermaster(loadout: &mut Loadout, budget: &mut Budget, rng: &mut ThreadRng) {
+pub fn always_quartermaster(
+    loadout: &mut Loadout,
+    budget: &mut Budget,
+    config: &Config,
+    rng: &mut StdRng,
+) {
     // Always replace the loadout with a valid quartermaster loadout.
 
     if loadout.weapon_one.locked || loadout.weapon_two.locked {
@@ -336,25 +751,38 @@ pub fn always_quartermaster(loadout: &mut Loadout, budget: &mut Budget, rng: &mu
     if let Some(weapon_one) = &loadout.weapon_one.item {
         let _tx = budget::process_transaction(
             budget,
-            Transaction::Weapon(true, weapon_one.get_cost(), weapon_one.to_full_name()),
+            Transaction::refund(
+                TransactionCategory::Weapon,
+                0,
+                weapon_one.get_cost(),
+                weapon_one.to_full_name(),
+            ),
         )
         .ok();
     }
 
     let weapons = CORE_SEARCH_UTIL.get_weapons_by_sizes(&[Slot::Large]);
-    let new_weapon = item_lte_cost(&weapons, budget.weapons_budget, rng);
+    let new_weapon = preferred_weapon_lte_cost(&weapons, 0, budget.weapons_budget, config, rng);
 
     loadout.weapon_one.item = if let Some(new_check_weapon) = &new_weapon {
         let tx_res = budget::process_transaction(
             budget,
-            Transaction::Weapon(
-                false,
+            Transaction::purchase(
+                TransactionCategory::Weapon,
+                0,
                 new_check_weapon.get_cost(),
                 new_check_weapon.to_full_name(),
             ),
         );
 
-        if let Err(_e) = tx_res {
+        if let Err(e) = tx_res {
+            loadout.errors.push(LoadoutError::from_transaction(
+                ERR_INSF_FND,
+                TransactionCategory::Weapon,
+                0,
+                0,
+                e,
+            ));
             None
         } else {
             new_weapon
@@ -366,25 +794,38 @@ pub fn always_quartermaster(loadout: &mut Loadout, budget: &mut Budget, rng: &mu
     if let Some(weapon_two) = &loadout.weapon_two.item {
         let _tx = budget::process_transaction(
             budget,
-            Transaction::Weapon(true, weapon_two.get_cost(), weapon_two.to_full_name()),
+            Transaction::refund(
+                TransactionCategory::Weapon,
+                1,
+                weapon_two.get_cost(),
+                weapon_two.to_full_name(),
+            ),
         )
         .ok();
     }
 
     let weapons = CORE_SEARCH_UTIL.get_weapons_by_sizes(&[Slot::Medium]);
-    let new_weapon = item_lte_cost(&weapons, budget.weapons_budget, rng);
+    let new_weapon = preferred_weapon_lte_cost(&weapons, 1, budget.weapons_budget, config, rng);
 
     loadout.weapon_two.item = if let Some(new_check_weapon) = &new_weapon {
         let tx_res = budget::process_transaction(
             budget,
-            Transaction::Weapon(
-                false,
+            Transaction::purchase(
+                TransactionCategory::Weapon,
+                1,
                 new_check_weapon.get_cost(),
                 new_check_weapon.to_full_name(),
             ),
         );
 
-        if let Err(_e) = tx_res {
+        if let Err(e) = tx_res {
+            loadout.errors.push(LoadoutError::from_transaction(
+                ERR_INSF_FND,
+                TransactionCategory::Weapon,
+                1,
+                0,
+                e,
+            ));
             None
         } else {
             new_weapon
@@ -396,7 +837,10 @@ pub fn always_quartermaster(loadout: &mut Loadout, budget: &mut Budget, rng: &mu
 
 pub fn always_dual_wield(
     budget: &mut Budget,
-    rng: &mut ThreadRng,
+    errors: &mut Vec<LoadoutError>,
+    config: &Config,
+    rng: &mut StdRng,
+    slot: u8,
     weapon: &mut GenericItemLockable,
 ) {
     // Temporary workaround to making cost work.
@@ -422,23 +866,37 @@ pub fn always_dual_wield(
 
         let _tx = budget::process_transaction(
             budget,
-            Transaction::Weapon(true, weapon_check.get_cost(), weapon_check.to_full_name()),
+            Transaction::refund(
+                TransactionCategory::Weapon,
+                slot,
+                weapon_check.get_cost(),
+                weapon_check.to_full_name(),
+            ),
         )
         .ok();
 
-        let mut new_weapon = item_lte_cost(&search_weapons, budget.weapons_budget, rng);
+        let mut new_weapon =
+            preferred_weapon_lte_cost(&search_weapons, slot, budget.weapons_budget, config, rng);
 
         if let Some(new_check_weapon) = &new_weapon {
             let tx_res = budget::process_transaction(
                 budget,
-                Transaction::Weapon(
-                    false,
+                Transaction::purchase(
+                    TransactionCategory::Weapon,
+                    slot,
                     new_check_weapon.get_cost(),
                     new_check_weapon.to_full_name(),
                 ),
             );
 
-            if let Err(_e) = tx_res {
+            if let Err(e) = tx_res {
+                errors.push(LoadoutError::from_transaction(
+                    ERR_INSF_FND,
+                    TransactionCategory::Weapon,
+                    slot,
+                    0,
+                    e,
+                ));
                 new_weapon = None;
             }
         }
@@ -450,7 +908,8 @@ pub fn always_dual_wield(
 pub fn dedupe_weapons(
     loadout: &mut Loadout,
     budget: &mut Budget,
-    rng: &mut ThreadRng,
+    config: &Config,
+    rng: &mut StdRng,
     quartermaster: bool,
 ) {
     match (&loadout.weapon_one, &loadout.weapon_two) {
@@ -469,14 +928,15 @@ pub fn dedupe_weapons(
             }
 
             let mut attempts = 0;
-            let _tx = transaction_from_weapon(budget, weapon_two, true).ok();
+            let _tx = transaction_from_weapon(budget, 1, weapon_two, true).ok();
             let mut new_weapon = weapon_two.clone();
 
             while weapon_one == &new_weapon && attempts != MAX_DUPE_CHECK_AMOUNT {
                 let slot = weapon_one.get_slot();
                 let valid_slots = get_valid_slots(quartermaster, &slot);
                 let weapons = CORE_SEARCH_UTIL.get_weapons_by_sizes(&valid_slots);
-                let rand_weapon = item_lte_cost(&weapons, budget.weapons_budget, rng);
+                let rand_weapon =
+                    preferred_weapon_lte_cost(&weapons, 1, budget.weapons_budget, config, rng);
 
                 if let Some(new_check_weapon) = rand_weapon {
                     new_weapon = new_check_weapon.clone();
@@ -487,9 +947,16 @@ pub fn dedupe_weapons(
 
             let new_weapon = Some(new_weapon);
             loadout.weapon_two.item = if let Some(new_check_weapon) = &new_weapon {
-                let tx_res = transaction_from_weapon(budget, new_check_weapon, false);
-
-                if let Err(_e) = tx_res {
+                let tx_res = transaction_from_weapon(budget, 1, new_check_weapon, false);
+
+                if let Err(e) = tx_res {
+                    loadout.errors.push(LoadoutError::from_transaction(
+                        ERR_INSF_FND,
+                        TransactionCategory::Weapon,
+                        1,
+                        0,
+                        e,
+                    ));
                     None
                 } else {
                     new_weapon
@@ -513,14 +980,15 @@ pub fn dedupe_weapons(
             }
 
             let mut attempts = 0;
-            let _tx = transaction_from_weapon(budget, weapon_one, true).ok();
+            let _tx = transaction_from_weapon(budget, 0, weapon_one, true).ok();
             let mut new_weapon = weapon_one.clone();
 
             while weapon_two == &new_weapon && attempts != MAX_DUPE_CHECK_AMOUNT {
                 let slot = weapon_two.get_slot();
                 let valid_slots = get_valid_slots(quartermaster, &slot);
                 let weapons = CORE_SEARCH_UTIL.get_weapons_by_sizes(&valid_slots);
-                let rand_weapon = item_lte_cost(&weapons, budget.weapons_budget, rng);
+                let rand_weapon =
+                    preferred_weapon_lte_cost(&weapons, 0, budget.weapons_budget, config, rng);
 
                 if let Some(new_check_weapon) = rand_weapon {
                     new_weapon = new_check_weapon.clone();
@@ -531,9 +999,16 @@ pub fn dedupe_weapons(
 
             let new_weapon = Some(new_weapon);
             loadout.weapon_one.item = if let Some(new_check_weapon) = &new_weapon {
-                let tx_res = transaction_from_weapon(budget, new_check_weapon, false);
-
-                if let Err(_e) = tx_res {
+                let tx_res = transaction_from_weapon(budget, 0, new_check_weapon, false);
+
+                if let Err(e) = tx_res {
+                    loadout.errors.push(LoadoutError::from_transaction(
+                        ERR_INSF_FND,
+                        TransactionCategory::Weapon,
+                        0,
+                        0,
+                        e,
+                    ));
                     None
                 } else {
                     new_weapon
@@ -563,31 +1038,16 @@ pub fn always_duplicate_weapons(loadout: &mut Loadout, budget: &mut Budget) {
             // We can only clone small or medium slot weapons
             Slot::Small | Slot::Medium => {
                 if let Some(weapon_two) = weapon_two {
-                    let _tx = budget::process_transaction(
-                        budget,
-                        Transaction::Weapon(true, weapon_two.get_cost(), weapon_two.to_full_name()),
-                    )
-                    .ok();
+                    let _tx = transaction_from_weapon(budget, 1, weapon_two, true).ok();
                 }
 
-                let tx_res = budget::process_transaction(
-                    budget,
-                    Transaction::Weapon(false, weapon_one.get_cost(), weapon_one.to_full_name()),
-                );
+                let tx_res = transaction_from_weapon(budget, 1, weapon_one, false);
 
                 if tx_res.is_ok() {
                     loadout.weapon_two = loadout.weapon_one.clone();
                 } else if let Some(weapon_two) = weapon_two {
                     // Reversing transaction, this should not fail.
-                    let _tx = budget::process_transaction(
-                        budget,
-                        Transaction::Weapon(
-                            false,
-                            weapon_two.get_cost(),
-                            weapon_two.to_full_name(),
-                        ),
-                    )
-                    .ok();
+                    let _tx = transaction_from_weapon(budget, 1, weapon_two, false).ok();
                 }
 
                 return;
@@ -610,31 +1070,16 @@ pub fn always_duplicate_weapons(loadout: &mut Loadout, budget: &mut Budget) {
         match weapon_two.get_slot() {
             Slot::Small | Slot::Medium => {
                 if let Some(weapon_one) = weapon_one {
-                    let _tx = budget::process_transaction(
-                        budget,
-                        Transaction::Weapon(true, weapon_one.get_cost(), weapon_one.to_full_name()),
-                    )
-                    .ok();
+                    let _tx = transaction_from_weapon(budget, 0, weapon_one, true).ok();
                 }
 
-                let tx_res = budget::process_transaction(
-                    budget,
-                    Transaction::Weapon(false, weapon_two.get_cost(), weapon_two.to_full_name()),
-                );
+                let tx_res = transaction_from_weapon(budget, 0, weapon_two, false);
 
                 if tx_res.is_ok() {
                     loadout.weapon_one = loadout.weapon_two.clone();
                 } else if let Some(weapon_one) = weapon_one {
                     // Reversing transaction, this should not fail.
-                    let _tx = budget::process_transaction(
-                        budget,
-                        Transaction::Weapon(
-                            false,
-                            weapon_one.get_cost(),
-                            weapon_one.to_full_name(),
-                        ),
-                    )
-                    .ok();
+                    let _tx = transaction_from_weapon(budget, 0, weapon_one, false).ok();
                 }
             }
             Slot::Large => {}
@@ -644,7 +1089,10 @@ pub fn always_duplicate_weapons(loadout: &mut Loadout, budget: &mut Budget) {
 
 pub fn custom_ammo(
     budget: &mut Budget,
-    rng: &mut ThreadRng,
+    errors: &mut Vec<LoadoutError>,
+    config: &Config,
+    rng: &mut StdRng,
+    slot: u8,
     weapon: &mut GenericItemLockable,
     always: bool,
 ) {
@@ -656,12 +1104,18 @@ pub fn custom_ammo(
         }
 
         if let Some(weapon) = &mut weapon.item {
-            for ammo_type in &weapon.ammo_equipped {
+            for (ammo_slot, ammo_type) in (0..).zip(&weapon.ammo_equipped) {
                 if ammo_type.1.is_some() {
-                    let tx_res = transaction_from_custom_ammo(budget, ammo_type);
-
-                    if tx_res.is_err() {
-                        // Handle not purchasable ammo.
+                    let tx_res = transaction_from_custom_ammo(budget, slot, ammo_type);
+
+                    if let Err(e) = tx_res {
+                        errors.push(LoadoutError::from_transaction(
+                            ERR_INSF_FND_LOCK,
+                            TransactionCategory::Bullet,
+                            slot,
+                            ammo_slot,
+                            e,
+                        ));
                     }
                 }
             }
@@ -671,36 +1125,60 @@ pub fn custom_ammo(
     }
 
     if let Some(weapon) = &mut weapon.item {
-        let bullet_types = weapon.get_bullet_variants();
+        let bullet_types = weapon
+            .get_bullet_variants()
+            .into_iter()
+            .filter(|ammo_type| config.ammo_allowed(weapon, ammo_type))
+            .collect::<Vec<CustomAmmo>>();
+        let bullet_types = config.theme_ammo(weapon, bullet_types);
 
         weapon.ammo_equipped = vec![];
 
+        // Nothing custom is allowed, so it's left with the default ammo.
         if bullet_types.is_empty() {
+            fill_default_ammo(weapon);
             return;
         }
 
+        let always = always || config.theme_needs_ammo(weapon, &bullet_types);
+
+        // An explosive weapon slot is only explosive with the ammo to go with it.
+        let explosive_ammo = if config.weapon_preferences.get(usize::from(slot))
+            == Some(&WeaponSlotPreference::Explosive)
+            && !Theme::Explosives.default_ammo_matches(weapon)
+        {
+            bullet_types
+                .iter()
+                .filter(|ammo_type| Theme::Explosives.ammo_matches(weapon, ammo_type))
+                .cloned()
+                .collect::<Vec<CustomAmmo>>()
+        } else {
+            vec![]
+        };
+
+        let always = always || !explosive_ammo.is_empty();
+        let bullet_types = if explosive_ammo.is_empty() {
+            bullet_types
+        } else {
+            explosive_ammo
+        };
+
         if always {
             let ammo_type = bullet_types[rng.gen_range(0..bullet_types.len())].clone();
-            let tx_res = transaction_from_custom_ammo(budget, &ammo_type);
-
-            if tx_res.is_ok() {
+            if purchase_custom_ammo(budget, errors, slot, 0, &ammo_type) {
                 weapon.ammo_equipped.push(ammo_type);
             }
 
             if weapon.additional_ammo_slots.unwrap_or(false) {
                 let ammo_type = bullet_types[rng.gen_range(0..bullet_types.len())].clone();
-                let tx_res = transaction_from_custom_ammo(budget, &ammo_type);
-
-                if tx_res.is_ok() {
+                if purchase_custom_ammo(budget, errors, slot, 1, &ammo_type) {
                     weapon.ammo_equipped.push(ammo_type);
                 }
             }
         } else {
             if rng.gen_bool(0.25) {
                 let ammo_type = bullet_types[rng.gen_range(0..bullet_types.len())].clone();
-                let tx_res = transaction_from_custom_ammo(budget, &ammo_type);
-
-                if tx_res.is_ok() {
+                if purchase_custom_ammo(budget, errors, slot, 0, &ammo_type) {
                     weapon.ammo_equipped.push(ammo_type);
                 }
             } else if let Some(bullet_size) = &weapon.get_bullet_size() {
@@ -709,9 +1187,7 @@ pub fn custom_ammo(
 
             if rng.gen_bool(0.25) && weapon.additional_ammo_slots.unwrap_or(false) {
                 let ammo_type = bullet_types[rng.gen_range(0..bullet_types.len())].clone();
-                let tx_res = transaction_from_custom_ammo(budget, &ammo_type);
-
-                if tx_res.is_ok() {
+                if purchase_custom_ammo(budget, errors, slot, 1, &ammo_type) {
                     weapon.ammo_equipped.push(ammo_type);
                 }
             } else if weapon.additional_ammo_slots.unwrap_or(false) {
@@ -721,29 +1197,99 @@ pub fn custom_ammo(
             }
         }
 
-        if let Some(bullet_size) = &weapon.get_bullet_size() {
-            if weapon.ammo_equipped.is_empty() && weapon.additional_ammo_slots.unwrap_or(false) {
-                weapon.ammo_equipped = vec![
-                    (bullet_size.clone(), None, 0),
-                    (bullet_size.clone(), None, 0),
-                ];
-            } else if weapon.ammo_equipped.is_empty() {
-                weapon.ammo_equipped = vec![(bullet_size.clone(), None, 0)];
-            } else if weapon.ammo_equipped.len() == 1
-                && weapon.additional_ammo_slots.unwrap_or(false)
-            {
-                weapon.ammo_equipped.push((bullet_size.clone(), None, 0));
-            }
+        fill_default_ammo(weapon);
+    }
+}
+
+// Any ammo slot left empty gets the weapons default ammo.
+fn fill_default_ammo(weapon: &mut GenericItem) {
+    if let Some(bullet_size) = &weapon.get_bullet_size() {
+        let slots = if weapon.additional_ammo_slots.unwrap_or(false) {
+            2
+        } else {
+            1
+        };
+
+        while weapon.ammo_equipped.len() < slots {
+            weapon.ammo_equipped.push((bullet_size.clone(), None, 0));
         }
     }
 }
 
-pub fn random_tools(
-    loadout: &mut Loadout,
+// Errors and ledger entries are recorded against slots, so they're moved along with the items when
+// the slots are rearranged. `new_slots[old_slot]` is where the item in `old_slot` ended up.
+fn move_slots(
+    errors: &mut [LoadoutError],
     budget: &mut Budget,
-    config: &Config,
-    rng: &mut ThreadRng,
+    category: TransactionCategory,
+    new_slots: &[u8],
 ) {
+    for slot in errors
+        .iter_mut()
+        .filter_map(|error| error.slot_mut(category))
+    {
+        if let Some(new_slot) = new_slots.get(usize::from(*slot)) {
+            *slot = *new_slot;
+        }
+    }
+
+    for transaction in &mut budget.transactions {
+        let moved = transaction.category == category
+            || (category == TransactionCategory::Weapon
+                && transaction.category == TransactionCategory::Bullet);
+
+        if let Some(new_slot) = new_slots
+            .get(usize::from(transaction.slot))
+            .filter(|_| moved)
+        {
+            transaction.slot = *new_slot;
+        }
+    }
+}
+
+// Moves filled slots in front of empty ones, locked slots stay where they are.
+fn sort_filled_first(
+    lockables: &mut [GenericItemLockable],
+    errors: &mut [LoadoutError],
+    budget: &mut Budget,
+    category: TransactionCategory,
+) {
+    let mut order = (0..lockables.len()).collect::<Vec<usize>>();
+
+    order.sort_by(|a, b| {
+        let (a, b) = (&lockables[*a], &lockables[*b]);
+
+        if a.locked || b.locked {
+            Ordering::Equal
+        } else if a.item.is_none() && b.item.is_some() {
+            Ordering::Greater
+        } else if b.item.is_none() && a.item.is_some() {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        }
+    });
+
+    let mut new_slots = vec![0; order.len()];
+
+    for (new_slot, old_slot) in (0..).zip(&order) {
+        new_slots[*old_slot] = new_slot;
+    }
+
+    let sorted = order
+        .iter()
+        .map(|pos| lockables[*pos].clone())
+        .collect::<Vec<GenericItemLockable>>();
+    lockables.clone_from_slice(&sorted);
+    move_slots(errors, budget, category, &new_slots);
+}
+
+/// Fills the unlocked tool slots.
+///
+/// # Panics
+///
+/// Never in practice, there are only four tool slots.
+pub fn random_tools(loadout: &mut Loadout, budget: &mut Budget, config: &Config, rng: &mut StdRng) {
     let check_clone = loadout.tools.clone();
 
     for (slot, tool) in check_clone.iter().enumerate() {
@@ -751,35 +1297,44 @@ pub fn random_tools(
             continue;
         }
 
-        let random_tools = match config.tool_preferences[slot] {
-            ToolSlotPreference::NoPreference => {
-                CORE_SEARCH_UTIL.tools.iter().collect::<Vec<&GenericItem>>()
-            }
-            ToolSlotPreference::Decoys => CORE_SEARCH_UTIL.get_decoy_tools(),
-            ToolSlotPreference::Tripmines => CORE_SEARCH_UTIL.get_trip_mines(),
-            ToolSlotPreference::Melee => CORE_SEARCH_UTIL.get_melee_tools(),
-            ToolSlotPreference::Throwable => CORE_SEARCH_UTIL.get_throwables(),
-            ToolSlotPreference::Medkit => CORE_SEARCH_UTIL
-                .tools
-                .iter()
-                .filter(|tool| tool.to_full_name().as_str() == "First Aid Kit")
-                .collect::<Vec<&GenericItem>>(),
-            ToolSlotPreference::Others => CORE_SEARCH_UTIL.get_other_tools(),
-        };
+        let random_tools = limits::within_max(
+            config,
+            loadout,
+            CORE_SEARCH_UTIL.get_tools_by_preference(config.tool_preferences[slot]),
+        );
 
-        let random_tool = item_lte_cost(&random_tools, budget.tools_budget, rng);
+        // Tools meeting a minimum go first, anything else still fills the slot.
+        let needed_tools = limits::needed(config, loadout, &random_tools)
+            .into_iter()
+            .filter(|tool| loadout.tools.iter().all(|t| t.item.as_ref() != Some(tool)))
+            .collect::<Vec<&GenericItem>>();
+        let random_tool = item_lte_cost(&needed_tools, budget.tools_budget, config, rng)
+            .or_else(|| item_lte_cost(&random_tools, budget.tools_budget, config, rng));
 
         if loadout.tools.iter().all(|t| t.item != random_tool) {
             loadout.tools[slot].item = random_tool.as_ref().and_then(|check_tool| {
                 let tx_res = budget::process_transaction(
                     budget,
-                    Transaction::Tool(false, check_tool.cost, check_tool.to_full_name()),
+                    Transaction::purchase(
+                        TransactionCategory::Tool,
+                        slot.try_into().expect(ERR_SLOT_GT_UNSIGNED),
+                        check_tool.cost,
+                        check_tool.to_full_name(),
+                    ),
                 );
 
-                if tx_res.is_ok() {
-                    Some(check_tool.clone())
-                } else {
-                    None
+                match tx_res {
+                    Ok(()) => Some(check_tool.clone()),
+                    Err(e) => {
+                        loadout.errors.push(LoadoutError::from_transaction(
+                            ERR_INSF_FND,
+                            TransactionCategory::Tool,
+                            slot.try_into().expect(ERR_SLOT_GT_UNSIGNED),
+                            0,
+                            e,
+                        ));
+                        None
+                    }
                 }
             });
         }
@@ -790,22 +1345,22 @@ pub fn random_tools(
         .iter()
         .all(|pref| *pref == ToolSlotPreference::NoPreference)
     {
-        loadout.tools.sort_by(|a, b| {
-            if a.locked || b.locked {
-                Ordering::Equal
-            } else if a.item.is_none() && b.item.is_some() {
-                Ordering::Greater
-            } else if b.item.is_none() && a.item.is_some() {
-                Ordering::Less
-            } else {
-                Ordering::Equal
-            }
-        });
+        sort_filled_first(
+            &mut loadout.tools,
+            &mut loadout.errors,
+            budget,
+            TransactionCategory::Tool,
+        );
     }
 }
 
-pub fn random_tool(loadout: &mut Loadout, budget: &mut Budget, config: &Config, slot: u8) {
-    let mut rng = thread_rng();
+pub fn random_tool(
+    loadout: &mut Loadout,
+    budget: &mut Budget,
+    config: &Config,
+    rng: &mut StdRng,
+    slot: u8,
+) {
     let mut previous_budget = budget.clone();
     let previous_tool = loadout.tools[slot as usize].clone();
 
@@ -822,106 +1377,165 @@ pub fn random_tool(loadout: &mut Loadout, budget: &mut Budget, config: &Config,
         })
         .collect::<Vec<GenericItem>>();
 
-    let random_tools = match config.tool_preferences[slot as usize] {
-        ToolSlotPreference::NoPreference => {
-            CORE_SEARCH_UTIL.tools.iter().collect::<Vec<&GenericItem>>()
-        }
-        ToolSlotPreference::Decoys => CORE_SEARCH_UTIL.get_decoy_tools(),
-        ToolSlotPreference::Tripmines => CORE_SEARCH_UTIL.get_trip_mines(),
-        ToolSlotPreference::Melee => CORE_SEARCH_UTIL.get_melee_tools(),
-        ToolSlotPreference::Throwable => CORE_SEARCH_UTIL.get_throwables(),
-        ToolSlotPreference::Medkit => CORE_SEARCH_UTIL
-            .tools
-            .iter()
-            .filter(|tool| tool.to_full_name().as_str() == "First Aid Kit")
-            .collect::<Vec<&GenericItem>>(),
-        ToolSlotPreference::Others => CORE_SEARCH_UTIL.get_other_tools(),
-    };
+    let random_tools =
+        CORE_SEARCH_UTIL.get_tools_by_preference(config.tool_preferences[slot as usize]);
 
     let random_tools = random_tools
         .iter()
-        .filter(|tool| !not_tools.contains(tool))
+        .filter(|tool| !not_tools.contains(tool) && config.item_allowed(tool))
         .copied()
         .collect::<Vec<&GenericItem>>();
+    let random_tools = config.theme_items(random_tools);
+
+    let mut others = loadout.clone();
+    others.tools[slot as usize].item = None;
+    let random_tools = limits::within_max(config, &others, random_tools);
 
     if !random_tools.is_empty() {
         loadout.tools[slot as usize].item =
             Some(random_tools[rng.gen_range(0..random_tools.len())].clone());
 
-        refund_item(&mut previous_budget, &previous_tool);
-        purchase_item(&mut previous_budget, &loadout.tools[slot as usize]);
+        refund_item(
+            &mut previous_budget,
+            &mut loadout.errors,
+            TransactionCategory::Tool,
+            slot,
+            &previous_tool,
+        );
+        purchase_item(
+            &mut previous_budget,
+            &mut loadout.errors,
+            TransactionCategory::Tool,
+            slot,
+            &loadout.tools[slot as usize],
+        );
         *budget = previous_budget;
     }
 }
 
-pub fn random_consumable(loadout: &mut Loadout, budget: &mut Budget, slot: u8) {
-    let mut rng = thread_rng();
+pub fn random_consumable(
+    loadout: &mut Loadout,
+    budget: &mut Budget,
+    config: &Config,
+    rng: &mut StdRng,
+    slot: u8,
+) {
     let mut previous_budget = budget.clone();
     let previous_consumable = loadout.consumables[slot as usize].clone();
 
     let random_consumables = CORE_SEARCH_UTIL
-        .consumables
-        .iter()
+        .get_consumables_by_preference(config.consumable_preferences[slot as usize])
+        .into_iter()
+        .filter(|consumable| config.item_allowed(consumable))
         .collect::<Vec<&GenericItem>>();
+    let random_consumables = config.theme_items(random_consumables);
+
+    let mut others = loadout.clone();
+    others.consumables[slot as usize].item = None;
+    let random_consumables = limits::within_max(config, &others, random_consumables);
 
     if !random_consumables.is_empty() {
         loadout.consumables[slot as usize].item =
             Some(random_consumables[rng.gen_range(0..random_consumables.len())].clone());
 
-        refund_item(&mut previous_budget, &previous_consumable);
-        purchase_item(&mut previous_budget, &loadout.consumables[slot as usize]);
+        refund_item(
+            &mut previous_budget,
+            &mut loadout.errors,
+            TransactionCategory::Consumable,
+            slot,
+            &previous_consumable,
+        );
+        purchase_item(
+            &mut previous_budget,
+            &mut loadout.errors,
+            TransactionCategory::Consumable,
+            slot,
+            &loadout.consumables[slot as usize],
+        );
         *budget = previous_budget;
     }
 }
 
-fn random_consumables(loadout: &mut Loadout, budget: &mut Budget, rng: &mut ThreadRng) {
-    for consumable in &mut loadout.consumables {
-        if consumable.locked {
+fn random_consumables(
+    loadout: &mut Loadout,
+    budget: &mut Budget,
+    config: &Config,
+    rng: &mut StdRng,
+) {
+    for slot in 0..loadout.consumables.len() {
+        if loadout.consumables[slot].locked {
             continue;
         }
 
-        let random_consumables = CORE_SEARCH_UTIL
-            .consumables
-            .iter()
-            .collect::<Vec<&GenericItem>>();
-        let random_consumable = item_lte_cost(&random_consumables, budget.consumables_budget, rng);
+        let random_consumables = limits::within_max(
+            config,
+            loadout,
+            CORE_SEARCH_UTIL.get_consumables_by_preference(config.consumable_preferences[slot]),
+        );
+
+        // Consumables meeting a minimum go first, anything else still fills the slot.
+        let random_consumable = item_lte_cost(
+            &limits::needed(config, loadout, &random_consumables),
+            budget.consumables_budget,
+            config,
+            rng,
+        )
+        .or_else(|| item_lte_cost(&random_consumables, budget.consumables_budget, config, rng));
+
+        let errors = &mut loadout.errors;
+        let slot_num = slot.try_into().expect(ERR_SLOT_GT_UNSIGNED);
 
-        consumable.item = random_consumable.as_ref().and_then(|check_consumable| {
+        loadout.consumables[slot].item = random_consumable.as_ref().and_then(|check_consumable| {
             let tx_res = budget::process_transaction(
                 budget,
-                Transaction::Consumable(
-                    false,
+                Transaction::purchase(
+                    TransactionCategory::Consumable,
+                    slot_num,
                     check_consumable.cost,
                     check_consumable.to_full_name(),
                 ),
             );
 
-            if tx_res.is_ok() {
-                Some(check_consumable.clone())
-            } else {
-                None
+            match tx_res {
+                Ok(()) => Some(check_consumable.clone()),
+                Err(e) => {
+                    errors.push(LoadoutError::from_transaction(
+                        ERR_INSF_FND,
+                        TransactionCategory::Consumable,
+                        slot_num,
+                        0,
+                        e,
+                    ));
+                    None
+                }
             }
         });
     }
 
-    loadout.consumables.sort_by(|a, b| {
-        if a.locked || b.locked {
-            Ordering::Equal
-        } else if a.item.is_none() && b.item.is_some() {
-            Ordering::Greater
-        } else if b.item.is_none() && a.item.is_some() {
-            Ordering::Less
-        } else {
-            Ordering::Equal
-        }
-    });
+    if config
+        .consumable_preferences
+        .iter()
+        .all(|pref| *pref == ConsumableSlotPreference::NoPreference)
+    {
+        sort_filled_first(
+            &mut loadout.consumables,
+            &mut loadout.errors,
+            budget,
+            TransactionCategory::Consumable,
+        );
+    }
 }
 
-pub fn random_weapon_one(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
+pub fn random_weapon_one(
+    loadout: &mut Loadout,
+    budget: &mut Budget,
+    config: &Config,
+    rng: &mut StdRng,
+) {
     let mut previous_budget = budget.clone();
     let previous_weapon = loadout.weapon_one.clone();
+    let previous_errors = loadout.errors.clone();
 
-    let mut rng = thread_rng();
     let initial_weapon_two_lock = loadout.weapon_two.locked;
 
     let mut weapon_one = loadout.weapon_one.clone();
@@ -929,8 +1543,9 @@ pub fn random_weapon_one(loadout: &mut Loadout, budget: &mut Budget, config: &Co
     initial_weapon(
         loadout,
         budget,
-        &mut rng,
-        config.option_exists(ToggleOption::Quartermaster),
+        config,
+        rng,
+        0,
         &mut weapon_one,
         &weapon_two,
     );
@@ -941,13 +1556,13 @@ pub fn random_weapon_one(loadout: &mut Loadout, budget: &mut Budget, config: &Co
     {
         // Lock weapon two since we ONLY want to ensure weapon one is changed.
         loadout.weapon_two.locked = true;
-        always_quartermaster(loadout, budget, &mut rng);
+        always_quartermaster(loadout, budget, config, rng);
         loadout.weapon_two.locked = initial_weapon_two_lock;
     }
 
     if config.option_exists(ToggleOption::AlwaysDualWield) {
         let mut weapon_one = loadout.weapon_one.clone();
-        always_dual_wield(budget, &mut rng, &mut weapon_one);
+        always_dual_wield(budget, &mut loadout.errors, config, rng, 0, &mut weapon_one);
         loadout.weapon_one = weapon_one.clone();
     }
 
@@ -957,7 +1572,8 @@ pub fn random_weapon_one(loadout: &mut Loadout, budget: &mut Budget, config: &Co
         dedupe_weapons(
             loadout,
             budget,
-            &mut rng,
+            config,
+            rng,
             config.option_exists(ToggleOption::Quartermaster),
         );
         loadout.weapon_two.locked = initial_weapon_two_lock;
@@ -967,7 +1583,10 @@ pub fn random_weapon_one(loadout: &mut Loadout, budget: &mut Budget, config: &Co
         let mut weapon_one = loadout.weapon_one.clone();
         custom_ammo(
             budget,
-            &mut rng,
+            &mut loadout.errors,
+            config,
+            rng,
+            0,
             &mut weapon_one,
             config.option_exists(ToggleOption::AlwaysCustomAmmo),
         );
@@ -983,16 +1602,35 @@ pub fn random_weapon_one(loadout: &mut Loadout, budget: &mut Budget, config: &Co
         loadout.weapon_two.locked = initial_weapon_two_lock;
     }
 
-    refund_item(&mut previous_budget, &previous_weapon);
-    purchase_item(&mut previous_budget, &loadout.weapon_one);
+    // The steps above charged a scratch budget, only the final purchase counts.
+    loadout.errors = previous_errors;
+    refund_item(
+        &mut previous_budget,
+        &mut loadout.errors,
+        TransactionCategory::Weapon,
+        0,
+        &previous_weapon,
+    );
+    purchase_item(
+        &mut previous_budget,
+        &mut loadout.errors,
+        TransactionCategory::Weapon,
+        0,
+        &loadout.weapon_one,
+    );
     *budget = previous_budget;
 }
 
-pub fn random_weapon_two(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
+pub fn random_weapon_two(
+    loadout: &mut Loadout,
+    budget: &mut Budget,
+    config: &Config,
+    rng: &mut StdRng,
+) {
     let mut previous_budget = budget.clone();
     let previous_weapon = loadout.weapon_two.clone();
+    let previous_errors = loadout.errors.clone();
 
-    let mut rng = thread_rng();
     let initial_weapon_one_lock = loadout.weapon_one.locked;
 
     let mut weapon_two = loadout.weapon_two.clone();
@@ -1000,8 +1638,9 @@ pub fn random_weapon_two(loadout: &mut Loadout, budget: &mut Budget, config: &Co
     initial_weapon(
         loadout,
         budget,
-        &mut rng,
-        config.option_exists(ToggleOption::Quartermaster),
+        config,
+        rng,
+        1,
         &mut weapon_two,
         &weapon_one,
     );
@@ -1012,13 +1651,13 @@ pub fn random_weapon_two(loadout: &mut Loadout, budget: &mut Budget, config: &Co
     {
         // Lock weapon one since we ONLY want to ensure weapon two is changed.
         loadout.weapon_one.locked = true;
-        always_quartermaster(loadout, budget, &mut rng);
+        always_quartermaster(loadout, budget, config, rng);
         loadout.weapon_one.locked = initial_weapon_one_lock;
     }
 
     if config.option_exists(ToggleOption::AlwaysDualWield) {
         let mut weapon_two = loadout.weapon_two.clone();
-        always_dual_wield(budget, &mut rng, &mut weapon_two);
+        always_dual_wield(budget, &mut loadout.errors, config, rng, 1, &mut weapon_two);
         loadout.weapon_two = weapon_two.clone();
     }
 
@@ -1028,7 +1667,8 @@ pub fn random_weapon_two(loadout: &mut Loadout, budget: &mut Budget, config: &Co
         dedupe_weapons(
             loadout,
             budget,
-            &mut rng,
+            config,
+            rng,
             config.option_exists(ToggleOption::Quartermaster),
         );
         loadout.weapon_one.locked = initial_weapon_one_lock;
@@ -1038,7 +1678,10 @@ pub fn random_weapon_two(loadout: &mut Loadout, budget: &mut Budget, config: &Co
         let mut weapon_two = loadout.weapon_two.clone();
         custom_ammo(
             budget,
-            &mut rng,
+            &mut loadout.errors,
+            config,
+            rng,
+            1,
             &mut weapon_two,
             config.option_exists(ToggleOption::AlwaysCustomAmmo),
         );
@@ -1054,53 +1697,151 @@ pub fn random_weapon_two(loadout: &mut Loadout, budget: &mut Budget, config: &Co
         loadout.weapon_one.locked = initial_weapon_one_lock;
     }
 
-    refund_item(&mut previous_budget, &previous_weapon);
-    purchase_item(&mut previous_budget, &loadout.weapon_two);
+    // The steps above charged a scratch budget, only the final purchase counts.
+    loadout.errors = previous_errors;
+    refund_item(
+        &mut previous_budget,
+        &mut loadout.errors,
+        TransactionCategory::Weapon,
+        1,
+        &previous_weapon,
+    );
+    purchase_item(
+        &mut previous_budget,
+        &mut loadout.errors,
+        TransactionCategory::Weapon,
+        1,
+        &loadout.weapon_two,
+    );
     *budget = previous_budget;
 }
 
-fn sort_weapons(loadout: &mut Loadout) {
-    if !loadout.weapon_one.locked && !loadout.weapon_two.locked {
-        match (&mut loadout.weapon_one.item, &mut loadout.weapon_two.item) {
-            (Some(weapon_one), Some(weapon_two)) => {
-                match (weapon_one.get_slot(), weapon_two.get_slot()) {
-                    (Slot::Small | Slot::Medium, Slot::Large) => {
-                        let weapon_one_clone = weapon_one.clone();
-                        *weapon_one = weapon_two.clone();
-                        *weapon_two = weapon_one_clone;
-                    }
-                    (Slot::Medium, Slot::Medium) | (Slot::Small, Slot::Small) => {
-                        match (weapon_one.get_bullet_size(), weapon_two.get_bullet_size()) {
-                            (
-                                Some(BulletSize::Compact)
-                                | Some(BulletSize::Medium)
-                                | Some(BulletSize::Special),
-                                Some(_),
-                            ) => {
-                                let weapon_one_clone = weapon_one.clone();
-                                *weapon_one = weapon_two.clone();
-                                *weapon_two = weapon_one_clone;
-                            }
-                            _ => {}
-                        }
-                    }
-                    _ => {}
-                }
-            }
-            (weapon_one @ None, weapon_two @ Some(_)) => {
-                *weapon_one = weapon_two.clone();
-                *weapon_two = None;
+pub fn sort_weapons(loadout: &mut Loadout, budget: &mut Budget) {
+    if loadout.weapon_one.locked || loadout.weapon_two.locked {
+        return;
+    }
+
+    let swap = match (&loadout.weapon_one.item, &loadout.weapon_two.item) {
+        (Some(weapon_one), Some(weapon_two)) => {
+            match (weapon_one.get_slot(), weapon_two.get_slot()) {
+                (Slot::Small | Slot::Medium, Slot::Large) => true,
+                (Slot::Medium, Slot::Medium) | (Slot::Small, Slot::Small) => matches!(
+                    (weapon_one.get_bullet_size(), weapon_two.get_bullet_size()),
+                    (
+                        Some(BulletSize::Compact | BulletSize::Medium | BulletSize::Special),
+                        Some(_)
+                    )
+                ),
+                _ => false,
             }
-            _ => {}
         }
+        (None, Some(_)) => true,
+        _ => false,
+    };
+
+    if swap {
+        std::mem::swap(&mut loadout.weapon_one, &mut loadout.weapon_two);
+        move_slots(
+            &mut loadout.errors,
+            budget,
+            TransactionCategory::Weapon,
+            &[1, 0],
+        );
+    }
+}
+
+// How many loadouts `random` can generate for `config` trying to reach the min cost.
+#[must_use]
+pub fn max_attempts(config: &Config) -> usize {
+    let in_range = config
+        .min_cost
+        .is_some_and(|min_cost| config.max_cost.is_none_or(|max_cost| max_cost >= min_cost));
+
+    // Best value already finds the most expensive loadout, trying again won't get any closer.
+    if in_range && !config.option_exists(ToggleOption::BestValue) {
+        MAX_SPEND_ATTEMPTS
+    } else {
+        1
     }
 }
 
 pub fn random(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
-    let mut rng = thread_rng();
+    // The seed is kept on the loadout so it can be shared, the same seed with the same config and
+    // locks will always produce the same loadout.
+    let seed = config.seed.unwrap_or_else(|| thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+    loadout.seed = Some(seed);
+    loadout.errors.clear();
+
+    let Some(min_cost) = config.min_cost else {
+        random_once(loadout, budget, config, &mut rng);
+        return;
+    };
+
+    if config.max_cost.is_some_and(|max_cost| max_cost < min_cost) {
+        random_once(loadout, budget, config, &mut rng);
+        loadout.errors.push(LoadoutError::Budget {
+            error: format!(
+                "Min cost {min_cost} is greater than max cost {}.",
+                config.max_cost.unwrap_or_default()
+            ),
+        });
+        return;
+    }
+
+    // Every attempt starts from the same locked items, the most expensive attempt is kept if none
+    // of them reach the minimum.
+    let initial_loadout = loadout.clone();
+    let mut best: Option<(Loadout, Budget)> = None;
+
+    for _ in 0..max_attempts(config) {
+        let mut attempt_loadout = initial_loadout.clone();
+        let mut attempt_budget = budget.clone();
+        random_once(&mut attempt_loadout, &mut attempt_budget, config, &mut rng);
+
+        let reached_min = attempt_budget.total_cost >= min_cost;
+
+        let is_best = match &best {
+            Some((_, best_budget)) => attempt_budget.total_cost > best_budget.total_cost,
+            None => true,
+        };
+
+        if is_best {
+            best = Some((attempt_loadout, attempt_budget));
+        }
+
+        if reached_min {
+            break;
+        }
+    }
+
+    if let Some((best_loadout, best_budget)) = best {
+        *loadout = best_loadout;
+        *budget = best_budget;
+    }
+
+    if budget.total_cost < min_cost {
+        loadout.errors.push(LoadoutError::Budget {
+            error: format!(
+                "Unable to spend at least {min_cost}, the closest loadout found costs {}.",
+                budget.total_cost
+            ),
+        });
+    }
+}
 
+fn random_once(loadout: &mut Loadout, budget: &mut Budget, config: &Config, rng: &mut StdRng) {
     if let Some(max_cost) = config.max_cost {
-        let _ = budget::set_budget(budget, max_cost);
+        budget.split.clone_from(&config.budget_split);
+        budget.leftover_policy = config.leftover_policy;
+
+        // Nothing can be bought without knowing how much each category gets.
+        if budget::set_budget(budget, max_cost).is_err() {
+            loadout.errors.push(LoadoutError::Budget {
+                error: ERR_BUDGETING.to_string(),
+            });
+            return;
+        }
     } else {
         *budget = Budget::default();
     }
@@ -1108,14 +1849,29 @@ pub fn random(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
     reset_tools(loadout, budget);
     reset_consumables(loadout, budget);
 
+    // Required items are locked in place while everything else is picked around them.
+    let required_slots = required::place_required(loadout, budget, config, rng);
+
+    if config.option_exists(ToggleOption::BestValue) {
+        best_value::best_value(loadout, budget, config, rng);
+    } else {
+        random_slots(loadout, budget, config, rng);
+    }
+
+    required::release_required(loadout, &required_slots);
+    limits::check_limits(loadout, config);
+}
+
+fn random_slots(loadout: &mut Loadout, budget: &mut Budget, config: &Config, rng: &mut StdRng) {
     // Run initial weapon check for weapon_one checking weapon_two.
     let mut weapon_one = loadout.weapon_one.clone();
     let mut weapon_two = loadout.weapon_two.clone();
     initial_weapon(
         loadout,
         budget,
-        &mut rng,
-        config.option_exists(ToggleOption::Quartermaster),
+        config,
+        rng,
+        0,
         &mut weapon_one,
         &weapon_two,
     );
@@ -1124,8 +1880,9 @@ pub fn random(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
     initial_weapon(
         loadout,
         budget,
-        &mut rng,
-        config.option_exists(ToggleOption::Quartermaster),
+        config,
+        rng,
+        1,
         &mut weapon_two,
         &weapon_one,
     );
@@ -1136,7 +1893,7 @@ pub fn random(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
     if config.option_exists(ToggleOption::Quartermaster)
         && config.option_exists(ToggleOption::AlwaysQuartermaster)
     {
-        always_quartermaster(loadout, budget, &mut rng);
+        always_quartermaster(loadout, budget, config, rng);
     }
 
     // If we requested always dual wield and the weapon isn't locked, always look for a random
@@ -1144,8 +1901,8 @@ pub fn random(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
     if config.option_exists(ToggleOption::AlwaysDualWield) {
         let mut weapon_one = loadout.weapon_one.clone();
         let mut weapon_two = loadout.weapon_two.clone();
-        always_dual_wield(budget, &mut rng, &mut weapon_one);
-        always_dual_wield(budget, &mut rng, &mut weapon_two);
+        always_dual_wield(budget, &mut loadout.errors, config, rng, 0, &mut weapon_one);
+        always_dual_wield(budget, &mut loadout.errors, config, rng, 1, &mut weapon_two);
         loadout.weapon_one = weapon_one.clone();
         loadout.weapon_two = weapon_two.clone();
     }
@@ -1157,7 +1914,8 @@ pub fn random(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
         dedupe_weapons(
             loadout,
             budget,
-            &mut rng,
+            config,
+            rng,
             config.option_exists(ToggleOption::Quartermaster),
         );
     }
@@ -1166,8 +1924,24 @@ pub fn random(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
         let mut weapon_one = loadout.weapon_one.clone();
         let mut weapon_two = loadout.weapon_two.clone();
         let always_custom_ammo = config.option_exists(ToggleOption::AlwaysCustomAmmo);
-        custom_ammo(budget, &mut rng, &mut weapon_one, always_custom_ammo);
-        custom_ammo(budget, &mut rng, &mut weapon_two, always_custom_ammo);
+        custom_ammo(
+            budget,
+            &mut loadout.errors,
+            config,
+            rng,
+            0,
+            &mut weapon_one,
+            always_custom_ammo,
+        );
+        custom_ammo(
+            budget,
+            &mut loadout.errors,
+            config,
+            rng,
+            1,
+            &mut weapon_two,
+            always_custom_ammo,
+        );
         loadout.weapon_one = weapon_one.clone();
         loadout.weapon_two = weapon_two.clone();
     }
@@ -1180,12 +1954,16 @@ pub fn random(loadout: &mut Loadout, budget: &mut Budget, config: &Config) {
 
     budget::transfer_weapons_to_tools(budget);
 
-    random_tools(loadout, budget, config, &mut rng);
+    random_tools(loadout, budget, config, rng);
 
     budget::transfer_tools_to_consumables(budget);
 
-    random_consumables(loadout, budget, &mut rng);
-    sort_weapons(loadout);
+    random_consumables(loadout, budget, config, rng);
+
+    // Sorting would move weapons out of the slot they were picked for.
+    if config.weapon_preferences == [WeaponSlotPreference::NoPreference; 2] {
+        sort_weapons(loadout, budget);
+    }
 }
 
 pub fn check_loadout_validity(loadout: &mut Loadout, quartermaster: bool) -> Vec<LoadoutInvalid> {
@@ -1224,3 +2002,43 @@ pub fn check_loadout_validity(loadout: &mut Loadout, quartermaster: bool) -> Vec
 
     invalid_checks
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::test_util::random_loadout;
+
+    #[test]
+    fn same_seed_same_loadout() {
+        for toggled_options in [vec![], vec![ToggleOption::BestValue]] {
+            let config = Config {
+                seed: Some(42),
+                max_cost: Some(2000),
+                min_cost: Some(1500),
+                toggled_options,
+                ..Config::default()
+            };
+
+            let (loadout, budget) = random_loadout(&config);
+
+            assert_eq!(loadout.seed, Some(42));
+            assert_eq!(random_loadout(&config), (loadout, budget));
+        }
+    }
+
+    #[test]
+    fn seed_regenerates_from_locks() {
+        let (mut loadout, _) = random_loadout(&Config::default());
+        loadout.weapon_one.locked = true;
+
+        let config = Config {
+            seed: loadout.seed,
+            ..Config::default()
+        };
+        let mut regenerated = loadout.clone();
+        random(&mut regenerated, &mut Budget::default(), &config);
+
+        assert_eq!(regenerated.weapon_one, loadout.weapon_one);
+        assert_eq!(regenerated.seed, loadout.seed);
+    }
+}
@@ -0,0 +1,295 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+use crate::content::{generic_item::GenericItemLockable, GenericItem, CORE_SEARCH_UTIL};
+use crate::randomizer::{loadout, Loadout, LoadoutInvalid};
+
+// Bump this whenever the layout below changes, or when items in the json files are reordered since
+// items are stored by their position.
+const CODE_VERSION: u8 = 1;
+
+const FLAG_QUARTERMASTER: u8 = 1;
+const FLAG_ITEM: u8 = 1;
+const FLAG_LOCKED: u8 = 1 << 1;
+const FLAG_DUAL_WIELD: u8 = 1 << 2;
+
+const ERR_INVALID_CODE: &str = "This loadout link is not valid.";
+const ERR_OLD_CODE: &str = "This loadout link is from a different version of the site.";
+const ERR_UNKNOWN_ITEM: &str = "This loadout link has an item that doesn't exist.";
+const ERR_UNKNOWN_AMMO: &str = "This loadout link has ammo the weapon can't use.";
+const ERR_DUAL_WIELD: &str = "This loadout link dual wields a weapon that can't be dual wielded.";
+
+// A loadout rebuilt from a code, `invalid` is from `loadout::check_loadout_validity`.
+#[derive(Debug, Clone)]
+pub struct SharedLoadout {
+    pub loadout: Loadout,
+    pub quartermaster: bool,
+    pub invalid: Vec<LoadoutInvalid>,
+}
+
+// Every slot is a flag byte, followed by the items position in `CORE_SEARCH_UTIL` when the slot
+// has an item. Weapons then list their ammo, 0 being the default ammo and anything else the
+// position in `GenericItem::get_bullet_variants` plus one.
+fn encode_item(bytes: &mut Vec<u8>, items: &[GenericItem], lockable: &GenericItemLockable) {
+    let mut flags = 0;
+
+    if lockable.locked {
+        flags |= FLAG_LOCKED;
+    }
+
+    let found = lockable.item.as_ref().and_then(|item| {
+        items
+            .iter()
+            .position(|check| check.to_full_name() == item.to_full_name())
+            .and_then(|pos| u16::try_from(pos).ok())
+            .map(|pos| (item, pos))
+    });
+
+    let Some((item, pos)) = found else {
+        bytes.push(flags);
+        return;
+    };
+
+    flags |= FLAG_ITEM;
+
+    if item.dual_wield {
+        flags |= FLAG_DUAL_WIELD;
+    }
+
+    bytes.push(flags);
+    bytes.extend_from_slice(&pos.to_be_bytes());
+}
+
+fn encode_ammo(bytes: &mut Vec<u8>, lockable: &GenericItemLockable) {
+    let Some(weapon) = &lockable.item else {
+        return;
+    };
+
+    let variants = weapon.get_bullet_variants();
+    let ammo = weapon
+        .ammo_equipped
+        .iter()
+        .map(|ammo| {
+            variants
+                .iter()
+                .position(|variant| ammo.1.is_some() && variant.0 == ammo.0 && variant.1 == ammo.1)
+                .and_then(|pos| u8::try_from(pos + 1).ok())
+                .unwrap_or(0)
+        })
+        .collect::<Vec<u8>>();
+
+    bytes.push(u8::try_from(ammo.len()).unwrap_or(0));
+    bytes.extend(ammo);
+}
+
+#[must_use]
+pub fn encode(loadout: &Loadout, quartermaster: bool) -> String {
+    let mut bytes = vec![
+        CODE_VERSION,
+        if quartermaster { FLAG_QUARTERMASTER } else { 0 },
+    ];
+
+    for weapon in [&loadout.weapon_one, &loadout.weapon_two] {
+        encode_item(&mut bytes, &CORE_SEARCH_UTIL.weapons, weapon);
+        encode_ammo(&mut bytes, weapon);
+    }
+
+    for tool in &loadout.tools {
+        encode_item(&mut bytes, &CORE_SEARCH_UTIL.tools, tool);
+    }
+
+    for consumable in &loadout.consumables {
+        encode_item(&mut bytes, &CORE_SEARCH_UTIL.consumables, consumable);
+    }
+
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+struct CodeReader {
+    bytes: Vec<u8>,
+    pos: usize,
+}
+
+impl CodeReader {
+    fn read_u8(&mut self) -> Result<u8, &'static str> {
+        let byte = self.bytes.get(self.pos).copied().ok_or(ERR_INVALID_CODE)?;
+        self.pos += 1;
+
+        Ok(byte)
+    }
+
+    fn read_u16(&mut self) -> Result<u16, &'static str> {
+        Ok(u16::from_be_bytes([self.read_u8()?, self.read_u8()?]))
+    }
+
+    fn read_item(&mut self, items: &[GenericItem]) -> Result<GenericItemLockable, &'static str> {
+        let flags = self.read_u8()?;
+
+        let item = if flags & FLAG_ITEM == 0 {
+            None
+        } else {
+            let mut item = items
+                .get(usize::from(self.read_u16()?))
+                .cloned()
+                .ok_or(ERR_UNKNOWN_ITEM)?;
+
+            if flags & FLAG_DUAL_WIELD != 0 {
+                if !item.can_dual_wield() {
+                    return Err(ERR_DUAL_WIELD);
+                }
+
+                item.dual_wield = true;
+            }
+
+            Some(item)
+        };
+
+        Ok(GenericItemLockable {
+            item,
+            locked: flags & FLAG_LOCKED != 0,
+        })
+    }
+
+    fn read_weapon(&mut self) -> Result<GenericItemLockable, &'static str> {
+        let mut weapon = self.read_item(&CORE_SEARCH_UTIL.weapons)?;
+
+        if let Some(weapon) = &mut weapon.item {
+            let variants = weapon.get_bullet_variants();
+            let count = self.read_u8()?;
+
+            // Weapons without ammo have no slots, and only some have a second one.
+            let ammo_slots = match (weapon.get_bullet_size(), weapon.additional_ammo_slots) {
+                (None, _) => 0,
+                (Some(_), Some(true)) => 2,
+                (Some(_), _) => 1,
+            };
+
+            if count > ammo_slots {
+                return Err(ERR_UNKNOWN_AMMO);
+            }
+
+            for _ in 0..count {
+                let ammo = match self.read_u8()? {
+                    0 => (weapon.get_bullet_size().ok_or(ERR_UNKNOWN_AMMO)?, None, 0),
+                    pos => variants
+                        .get(usize::from(pos - 1))
+                        .cloned()
+                        .ok_or(ERR_UNKNOWN_AMMO)?,
+                };
+
+                weapon.ammo_equipped.push(ammo);
+            }
+        }
+
+        Ok(weapon)
+    }
+}
+
+/// Rebuilds a loadout from a code made by `encode`.
+///
+/// # Errors
+///
+/// When the code isn't valid, is from another `CODE_VERSION` or has items or ammo that don't exist.
+pub fn decode(code: &str) -> Result<SharedLoadout, &'static str> {
+    let mut reader = CodeReader {
+        bytes: URL_SAFE_NO_PAD.decode(code).map_err(|_| ERR_INVALID_CODE)?,
+        pos: 0,
+    };
+
+    if reader.read_u8()? != CODE_VERSION {
+        return Err(ERR_OLD_CODE);
+    }
+
+    let quartermaster = reader.read_u8()? & FLAG_QUARTERMASTER != 0;
+
+    let mut loadout = Loadout {
+        weapon_one: reader.read_weapon()?,
+        weapon_two: reader.read_weapon()?,
+        ..Loadout::default()
+    };
+
+    for tool in &mut loadout.tools {
+        *tool = reader.read_item(&CORE_SEARCH_UTIL.tools)?;
+    }
+
+    for consumable in &mut loadout.consumables {
+        *consumable = reader.read_item(&CORE_SEARCH_UTIL.consumables)?;
+    }
+
+    if reader.pos != reader.bytes.len() {
+        return Err(ERR_INVALID_CODE);
+    }
+
+    let invalid = loadout::check_loadout_validity(&mut loadout, quartermaster);
+
+    Ok(SharedLoadout {
+        loadout,
+        quartermaster,
+        invalid,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::test_util::armed_loadout;
+
+    #[test]
+    fn round_trip() {
+        let loadout = armed_loadout();
+
+        let shared = decode(&encode(&loadout, false)).unwrap();
+
+        assert!(!shared.quartermaster);
+        assert!(shared.invalid.is_empty());
+        assert_eq!(shared.loadout.weapon_one, loadout.weapon_one);
+        assert_eq!(shared.loadout.weapon_two, loadout.weapon_two);
+        assert_eq!(shared.loadout.tools, loadout.tools);
+        assert_eq!(shared.loadout.consumables, loadout.consumables);
+    }
+
+    #[test]
+    fn round_trip_quartermaster() {
+        let shared = decode(&encode(&Loadout::default(), true)).unwrap();
+
+        assert!(shared.quartermaster);
+    }
+
+    #[test]
+    fn rejects_ammo_without_a_slot() {
+        let mut loadout = armed_loadout();
+        if let Some(weapon) = &mut loadout.weapon_two.item {
+            weapon.ammo_equipped.push(weapon.ammo_equipped[0].clone());
+        }
+
+        assert_eq!(
+            decode(&encode(&loadout, false)).unwrap_err(),
+            ERR_UNKNOWN_AMMO
+        );
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut bytes = URL_SAFE_NO_PAD
+            .decode(encode(&armed_loadout(), false))
+            .unwrap();
+        bytes[0] = CODE_VERSION + 1;
+
+        assert_eq!(
+            decode(&URL_SAFE_NO_PAD.encode(bytes)).unwrap_err(),
+            ERR_OLD_CODE
+        );
+    }
+
+    #[test]
+    fn rejects_trailing_bytes() {
+        let mut bytes = URL_SAFE_NO_PAD
+            .decode(encode(&armed_loadout(), false))
+            .unwrap();
+        bytes.push(0);
+
+        assert_eq!(
+            decode(&URL_SAFE_NO_PAD.encode(bytes)).unwrap_err(),
+            ERR_INVALID_CODE
+        );
+    }
+}
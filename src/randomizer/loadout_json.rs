@@ -0,0 +1,326 @@
+//! JSON export and import of a full loadout.
+//!
+//! ```json
+//! {
+//!     "version": 1,
+//!     "quartermaster": false,
+//!     "weapon_one": {
+//!         "name": "Sparks LRR",
+//!         "locked": true,
+//!         "dual_wield": false,
+//!         "ammo_equipped": [["long", "high_velocity", 10]]
+//!     },
+//!     "weapon_two": { "name": null, "locked": false },
+//!     "tools": [{ "name": "First Aid Kit", "locked": false }, ...],
+//!     "consumables": [{ "name": "Vitality Shot", "locked": false }, ...]
+//! }
+//! ```
+//!
+//! Names are the full item names shown on the site. Every `ammo_equipped` entry is the bullet size,
+//! the ammo variant (`null` for the weapons default ammo) and its cost, the cost is ignored on
+//! import and taken from the item data instead. `dual_wield` and `ammo_equipped` can be left out.
+
+use serde::{Deserialize, Serialize};
+
+use crate::content::{
+    generic_item::{CustomAmmo, GenericItemLockable},
+    GenericItem, CORE_SEARCH_UTIL,
+};
+use crate::randomizer::{loadout, Loadout, LoadoutInvalid};
+
+// Bump this whenever the format above changes in a way older exports can't be read.
+pub const JSON_VERSION: u32 = 1;
+
+const ERR_OLD_JSON: &str = "This loadout was exported from a different version of the site.";
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SlotJson {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub dual_wield: bool,
+    #[serde(default)]
+    pub ammo_equipped: Vec<CustomAmmo>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LoadoutJson {
+    pub version: u32,
+    #[serde(default)]
+    pub quartermaster: bool,
+    pub weapon_one: SlotJson,
+    pub weapon_two: SlotJson,
+    pub tools: [SlotJson; 4],
+    pub consumables: [SlotJson; 4],
+}
+
+// An imported loadout, `problems` lists everything that was dropped on the way in.
+#[derive(Debug, Clone)]
+pub struct ImportedLoadout {
+    pub loadout: Loadout,
+    pub quartermaster: bool,
+    pub invalid: Vec<LoadoutInvalid>,
+    pub problems: Vec<String>,
+}
+
+fn slot_json(lockable: &GenericItemLockable) -> SlotJson {
+    SlotJson {
+        name: lockable
+            .item
+            .as_ref()
+            .map(|item| item.to_full_name().trim().to_string()),
+        locked: lockable.locked,
+        dual_wield: lockable.item.as_ref().is_some_and(|item| item.dual_wield),
+        ammo_equipped: lockable
+            .item
+            .as_ref()
+            .map_or_else(Vec::new, |item| item.ammo_equipped.clone()),
+    }
+}
+
+#[must_use]
+pub fn export(loadout: &Loadout, quartermaster: bool) -> String {
+    let json = LoadoutJson {
+        version: JSON_VERSION,
+        quartermaster,
+        weapon_one: slot_json(&loadout.weapon_one),
+        weapon_two: slot_json(&loadout.weapon_two),
+        tools: loadout.tools.clone().map(|tool| slot_json(&tool)),
+        consumables: loadout
+            .consumables
+            .clone()
+            .map(|consumable| slot_json(&consumable)),
+    };
+
+    serde_json::to_string_pretty(&json).unwrap_or_default()
+}
+
+fn import_item(
+    problems: &mut Vec<String>,
+    items: &[GenericItem],
+    slot_name: &str,
+    slot: &SlotJson,
+) -> GenericItemLockable {
+    let item = slot.name.as_ref().and_then(|name| {
+        let found = items
+            .iter()
+            .find(|item| item.to_full_name().trim() == name.trim())
+            .cloned();
+
+        if found.is_none() {
+            problems.push(format!("{slot_name}: Unknown item {name}."));
+        }
+
+        found
+    });
+
+    let item = item.map(|mut item| {
+        if slot.dual_wield {
+            if item.can_dual_wield() {
+                item.dual_wield = true;
+            } else {
+                problems.push(format!(
+                    "{slot_name}: {} can't be dual wielded.",
+                    item.to_full_name()
+                ));
+            }
+        }
+
+        item
+    });
+
+    GenericItemLockable {
+        item,
+        locked: slot.locked,
+    }
+}
+
+// Ammo is matched against what the weapon can use so the costs come from the item data.
+fn import_ammo(
+    problems: &mut Vec<String>,
+    slot_name: &str,
+    slot: &SlotJson,
+    weapon: &mut GenericItemLockable,
+) {
+    let Some(item) = &mut weapon.item else {
+        return;
+    };
+
+    let Some(bullet_size) = item.get_bullet_size() else {
+        if !slot.ammo_equipped.is_empty() {
+            problems.push(format!(
+                "{slot_name}: {} doesn't use ammo.",
+                item.to_full_name()
+            ));
+        }
+        return;
+    };
+
+    let variants = item.get_bullet_variants();
+    let ammo_slots = if item.additional_ammo_slots.unwrap_or(false) {
+        2
+    } else {
+        1
+    };
+
+    for ammo in &slot.ammo_equipped {
+        let found = match &ammo.1 {
+            None if ammo.0 == bullet_size => Some((bullet_size.clone(), None, 0)),
+            None => None,
+            Some(variant) => variants
+                .iter()
+                .find(|check| check.0 == ammo.0 && check.1.as_ref() == Some(variant))
+                .cloned(),
+        };
+
+        match found {
+            Some(_) if item.ammo_equipped.len() >= ammo_slots => problems.push(format!(
+                "{slot_name}: {} only has {ammo_slots} ammo slot(s).",
+                item.to_full_name()
+            )),
+            Some(found) => item.ammo_equipped.push(found),
+            None => problems.push(format!(
+                "{slot_name}: {} can't use {} ammo.",
+                item.to_full_name(),
+                ammo.1
+                    .as_ref()
+                    .map_or_else(|| ammo.0.to_string(), ToString::to_string)
+            )),
+        }
+    }
+
+    // Anything missing is filled in with the default ammo, the same as a picked weapon.
+    while item.ammo_equipped.len() < ammo_slots {
+        item.ammo_equipped.push((bullet_size.clone(), None, 0));
+    }
+}
+
+/// Reads a loadout made by `export`, anything that can't be imported is listed in `problems`.
+///
+/// # Errors
+///
+/// When the json can't be parsed or is from another `JSON_VERSION`.
+pub fn import(json: &str) -> Result<ImportedLoadout, String> {
+    let json = serde_json::from_str::<LoadoutJson>(json)
+        .map_err(|err| format!("This isn't a valid loadout: {err}"))?;
+
+    if json.version != JSON_VERSION {
+        return Err(ERR_OLD_JSON.to_string());
+    }
+
+    let mut problems = vec![];
+    let mut loadout = Loadout::default();
+
+    for (slot_name, slot, weapon) in [
+        ("Weapon 1", &json.weapon_one, &mut loadout.weapon_one),
+        ("Weapon 2", &json.weapon_two, &mut loadout.weapon_two),
+    ] {
+        *weapon = import_item(&mut problems, &CORE_SEARCH_UTIL.weapons, slot_name, slot);
+        import_ammo(&mut problems, slot_name, slot, weapon);
+    }
+
+    for (pos, (slot, tool)) in json.tools.iter().zip(&mut loadout.tools).enumerate() {
+        let slot_name = format!("Tool {}", pos + 1);
+        *tool = import_item(&mut problems, &CORE_SEARCH_UTIL.tools, &slot_name, slot);
+    }
+
+    for (pos, (slot, consumable)) in json
+        .consumables
+        .iter()
+        .zip(&mut loadout.consumables)
+        .enumerate()
+    {
+        let slot_name = format!("Consumable {}", pos + 1);
+        *consumable = import_item(
+            &mut problems,
+            &CORE_SEARCH_UTIL.consumables,
+            &slot_name,
+            slot,
+        );
+    }
+
+    let invalid = loadout::check_loadout_validity(&mut loadout, json.quartermaster);
+
+    Ok(ImportedLoadout {
+        loadout,
+        quartermaster: json.quartermaster,
+        invalid,
+        problems,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randomizer::{
+        test_util::{armed_loadout, random_loadout},
+        Config,
+    };
+
+    #[test]
+    fn round_trip() {
+        let mut loadout = armed_loadout();
+        loadout.tools[0].locked = true;
+
+        let imported = import(&export(&loadout, true)).unwrap();
+
+        assert!(imported.quartermaster);
+        assert!(imported.problems.is_empty(), "{:?}", imported.problems);
+        assert_eq!(imported.loadout.weapon_one, loadout.weapon_one);
+        assert_eq!(imported.loadout.weapon_two, loadout.weapon_two);
+        assert_eq!(imported.loadout.tools, loadout.tools);
+        assert_eq!(imported.loadout.consumables, loadout.consumables);
+    }
+
+    #[test]
+    fn missing_ammo_is_default_ammo() {
+        let loadout = armed_loadout();
+        let mut json = serde_json::from_str::<LoadoutJson>(&export(&loadout, false)).unwrap();
+        json.weapon_one.ammo_equipped.clear();
+
+        let imported = import(&serde_json::to_string(&json).unwrap()).unwrap();
+        let weapon = imported.loadout.weapon_one.item.unwrap();
+        let bullet_size = weapon.get_bullet_size().unwrap();
+
+        assert!(imported.problems.is_empty(), "{:?}", imported.problems);
+        assert_eq!(weapon.ammo_equipped, vec![(bullet_size, None, 0); 2]);
+    }
+
+    #[test]
+    fn reports_ammo_without_a_slot() {
+        let loadout = armed_loadout();
+        let mut json = serde_json::from_str::<LoadoutJson>(&export(&loadout, false)).unwrap();
+        let ammo = json.weapon_two.ammo_equipped[0].clone();
+        json.weapon_two.ammo_equipped.push(ammo);
+
+        let imported = import(&serde_json::to_string(&json).unwrap()).unwrap();
+
+        assert_eq!(imported.loadout.weapon_two, loadout.weapon_two);
+        assert_eq!(imported.problems.len(), 1);
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let (loadout, _) = random_loadout(&Config::default());
+        let mut json = serde_json::from_str::<LoadoutJson>(&export(&loadout, false)).unwrap();
+        json.version = JSON_VERSION + 1;
+
+        assert_eq!(
+            import(&serde_json::to_string(&json).unwrap()).unwrap_err(),
+            ERR_OLD_JSON
+        );
+    }
+
+    #[test]
+    fn reports_unknown_items() {
+        let (loadout, _) = random_loadout(&Config::default());
+        let mut json = serde_json::from_str::<LoadoutJson>(&export(&loadout, false)).unwrap();
+        json.tools[0].name = Some("Not A Tool".to_string());
+
+        let imported = import(&serde_json::to_string(&json).unwrap()).unwrap();
+
+        assert!(imported.loadout.tools[0].item.is_none());
+        assert_eq!(imported.problems.len(), 1);
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::Write;
+
+use crate::content::{
+    generic_item::{CustomAmmo, GenericItemLockable},
+    GenericItem,
+};
+use crate::randomizer::{Budget, Loadout};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextFormat {
+    Plain,
+    // Discord flavoured, bold headers and a list of weapons.
+    Markdown,
+}
+
+// Default ammo has no variant so it's named after its size instead.
+pub fn ammo_name(ammo: &CustomAmmo) -> String {
+    ammo.1
+        .as_ref()
+        .map_or_else(|| ammo.0.to_string(), ToString::to_string)
+}
+
+fn item_names(items: &[GenericItemLockable]) -> String {
+    let names = items
+        .iter()
+        .filter_map(|item| item.item.as_ref())
+        .map(|item| item.to_full_name().trim().to_string())
+        .collect::<Vec<String>>();
+
+    if names.is_empty() {
+        "None".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+fn weapon_line(weapon: &GenericItem, format: TextFormat) -> String {
+    let name = weapon.to_full_name().trim().to_string();
+    let ammo = weapon
+        .ammo_equipped
+        .iter()
+        .map(ammo_name)
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    match format {
+        TextFormat::Plain => {
+            let dual_wield = if weapon.dual_wield {
+                " (Dual Wield)"
+            } else {
+                ""
+            };
+
+            if ammo.is_empty() {
+                format!("  {name}{dual_wield}")
+            } else {
+                format!("  {name}{dual_wield} [{ammo}]")
+            }
+        }
+        TextFormat::Markdown => {
+            let dual_wield = if weapon.dual_wield {
+                " *(Dual Wield)*"
+            } else {
+                ""
+            };
+
+            if ammo.is_empty() {
+                format!("- {name}{dual_wield}")
+            } else {
+                format!("- {name}{dual_wield} - {ammo}")
+            }
+        }
+    }
+}
+
+// A short summary of the loadout for pasting into chat.
+#[must_use]
+pub fn format_loadout(loadout: &Loadout, budget: &Budget, format: TextFormat) -> String {
+    let header = |title: &str| match format {
+        TextFormat::Plain => format!("{title}:"),
+        TextFormat::Markdown => format!("**{title}:**"),
+    };
+
+    let mut text = header("Weapons");
+
+    for weapon in [&loadout.weapon_one, &loadout.weapon_two]
+        .into_iter()
+        .filter_map(|weapon| weapon.item.as_ref())
+    {
+        text.push('\n');
+        text.push_str(&weapon_line(weapon, format));
+    }
+
+    let _ = write!(
+        text,
+        "\n{} {}\n{} {}\n{} {}",
+        header("Tools"),
+        item_names(&loadout.tools),
+        header("Consumables"),
+        item_names(&loadout.consumables),
+        header("Total Cost"),
+        budget.total_cost,
+    );
+
+    text
+}
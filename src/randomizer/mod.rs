@@ -1,13 +1,29 @@
 //! This is for the loadout randomizer seen in `src/pages/loadout.rs`
 
+pub mod balance;
+pub mod best_value;
 pub mod budget;
 pub mod config;
+pub mod limits;
 pub mod loadout;
+pub mod loadout_code;
+pub mod loadout_json;
+pub mod loadout_text;
+pub mod preset;
+pub mod required;
+pub mod squad;
+#[cfg(test)]
+mod test_util;
+pub mod theme;
+pub mod unlock_path;
+pub mod unlocks;
 
 pub use budget::Budget;
 pub use config::Config;
-pub use loadout::Loadout;
+pub use loadout::{random, Loadout};
+pub use unlocks::Unlocks;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LoadoutInvalid {
     WeaponSlot(u8),
     ToolSlot(u8),
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+use crate::content::{ToolSlotPreference, WeaponSlotPreference};
+use crate::randomizer::{config::ToggleOption, Config};
+
+// Bump this when a `Config` field changes meaning, new fields don't need a bump since they're filled
+// in from `Config::default` when an older preset is read.
+pub const PRESET_VERSION: u32 = 1;
+
+pub const ERR_EMPTY_NAME: &str = "A preset needs a name.";
+pub const ERR_NAME_TAKEN: &str = "There is already a preset with that name.";
+pub const ERR_BUILT_IN: &str = "Built-in presets can't be overwritten.";
+pub const ERR_UNKNOWN_PRESET: &str = "That preset doesn't exist.";
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Preset {
+    pub name: String,
+    pub version: u32,
+    pub config: Config,
+}
+
+impl Preset {
+    #[must_use]
+    pub fn new(name: &str, config: &Config) -> Self {
+        // A preset is a rule set, keeping the seed would make it give the same loadout every time.
+        Self {
+            name: name.trim().to_string(),
+            version: PRESET_VERSION,
+            config: Config {
+                seed: None,
+                ..config.clone()
+            },
+        }
+    }
+
+    // Applies the preset on top of `current`, unlocks aren't part of a preset so they're kept.
+    // Older saved presets can still have a seed, so it's dropped here as well.
+    #[must_use]
+    pub fn apply(&self, current: &Config) -> Config {
+        Config {
+            unlocks: current.unlocks.clone(),
+            seed: None,
+            ..self.config.clone()
+        }
+    }
+}
+
+#[must_use]
+pub fn built_in_presets() -> Vec<Preset> {
+    vec![
+        Preset::new("Default", &Config::default()),
+        Preset::new(
+            "Pistols Only, 400 Budget",
+            &Config {
+                max_cost: Some(400),
+                weapon_preferences: [WeaponSlotPreference::Pistol; 2],
+                tool_preferences: [
+                    ToolSlotPreference::Medkit,
+                    ToolSlotPreference::Melee,
+                    ToolSlotPreference::NoPreference,
+                    ToolSlotPreference::NoPreference,
+                ],
+                ..Config::default()
+            },
+        ),
+        Preset::new(
+            "Quartermaster Chaos",
+            &Config {
+                toggled_options: vec![
+                    ToggleOption::DualWield,
+                    ToggleOption::DuplicateWeapons,
+                    ToggleOption::CustomAmmo,
+                    ToggleOption::Quartermaster,
+                    ToggleOption::AlwaysCustomAmmo,
+                    ToggleOption::AlwaysQuartermaster,
+                ],
+                ..Config::default()
+            },
+        ),
+        Preset::new(
+            "No Custom Ammo",
+            &Config {
+                toggled_options: vec![ToggleOption::DualWield, ToggleOption::DuplicateWeapons],
+                ..Config::default()
+            },
+        ),
+    ]
+}
+
+// Each preset is read on its own so one that can't be read doesn't lose the rest. Presets saved by
+// a newer version of the site are dropped too since their config can't be trusted.
+#[must_use]
+pub fn readable_presets(saved: Vec<serde_json::Value>) -> Vec<Preset> {
+    saved
+        .into_iter()
+        .filter_map(|preset| match serde_json::from_value::<Preset>(preset) {
+            Ok(preset) if preset.version <= PRESET_VERSION => Some(preset),
+            Ok(preset) => {
+                log::warn!(
+                    "Ignoring preset {} saved with preset version {}",
+                    preset.name,
+                    preset.version
+                );
+                None
+            }
+            Err(err) => {
+                log::error!("Unable to read a saved preset: {err}");
+                None
+            }
+        })
+        .collect()
+}
+
+fn name_taken(saved: &[Preset], name: &str, skip: Option<usize>) -> bool {
+    built_in_presets()
+        .iter()
+        .chain(
+            saved
+                .iter()
+                .enumerate()
+                .filter(|(pos, _)| Some(*pos) != skip)
+                .map(|(_, preset)| preset),
+        )
+        .any(|preset| preset.name.eq_ignore_ascii_case(name))
+}
+
+/// Saving under the name of an existing saved preset overwrites it.
+///
+/// # Errors
+///
+/// When the name is empty or belongs to a built-in preset.
+pub fn save_preset(
+    saved: &mut Vec<Preset>,
+    name: &str,
+    config: &Config,
+) -> Result<(), &'static str> {
+    let preset = Preset::new(name, config);
+
+    if preset.name.is_empty() {
+        return Err(ERR_EMPTY_NAME);
+    }
+
+    if let Some(pos) = saved
+        .iter()
+        .position(|check| check.name.eq_ignore_ascii_case(&preset.name))
+    {
+        saved[pos] = preset;
+        return Ok(());
+    }
+
+    if name_taken(saved, &preset.name, None) {
+        return Err(ERR_BUILT_IN);
+    }
+
+    saved.push(preset);
+
+    Ok(())
+}
+
+/// # Errors
+///
+/// When the name is empty or taken, or there's no saved preset at `pos`.
+pub fn rename_preset(saved: &mut [Preset], pos: usize, name: &str) -> Result<(), &'static str> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(ERR_EMPTY_NAME);
+    }
+
+    if name_taken(saved, name, Some(pos)) {
+        return Err(ERR_NAME_TAKEN);
+    }
+
+    let preset = saved.get_mut(pos).ok_or(ERR_UNKNOWN_PRESET)?;
+    preset.name = name.to_string();
+
+    Ok(())
+}
+
+/// # Errors
+///
+/// When there's no saved preset at `pos`.
+pub fn delete_preset(saved: &mut Vec<Preset>, pos: usize) -> Result<(), &'static str> {
+    if pos >= saved.len() {
+        return Err(ERR_UNKNOWN_PRESET);
+    }
+
+    saved.remove(pos);
+
+    Ok(())
+}
@@ -0,0 +1,415 @@
+use rand::{rngs::StdRng, seq::SliceRandom};
+
+use crate::content::{generic_item::GenericItemLockable, GenericItem, CORE_SEARCH_UTIL};
+use crate::randomizer::{
+    budget::{self, Transaction, TransactionCategory},
+    config::ToggleOption,
+    loadout::{self, LoadoutError},
+    unlocks::UnlockKey,
+    Budget, Config, Loadout,
+};
+
+const ERR_UNKNOWN_ITEM: &str = "it isn't a weapon, tool or consumable";
+const ERR_NO_SLOT: &str = "there's no free slot it fits in";
+const ERR_NO_FUNDS: &str = "it doesn't fit in the budget";
+const ERR_DUPLICATE_TOOL: &str = "the same tool can't be taken twice";
+const ERR_NOT_ALLOWED: &str = "it's above the max rank, excluded or not unlocked";
+
+// Slots holding a required item, these are locked while the rest of the loadout is generated.
+pub type RequiredSlots = Vec<(TransactionCategory, usize)>;
+
+fn get_item(key: &UnlockKey) -> Option<(TransactionCategory, &'static GenericItem)> {
+    match key {
+        UnlockKey::Weapon { name, variant } => CORE_SEARCH_UTIL
+            .get_weapon(name, variant.as_ref())
+            .map(|weapon| (TransactionCategory::Weapon, weapon)),
+        UnlockKey::Tool(name) => CORE_SEARCH_UTIL
+            .get_tool(name)
+            .map(|tool| (TransactionCategory::Tool, tool)),
+        UnlockKey::Consumable(name) => CORE_SEARCH_UTIL
+            .get_consumable(name)
+            .map(|consumable| (TransactionCategory::Consumable, consumable)),
+        UnlockKey::Ammo { .. } => None,
+    }
+}
+
+const fn slots_mut(
+    loadout: &mut Loadout,
+    category: TransactionCategory,
+) -> &mut [GenericItemLockable] {
+    match category {
+        TransactionCategory::Tool => &mut loadout.tools,
+        TransactionCategory::Consumable => &mut loadout.consumables,
+        TransactionCategory::Weapon | TransactionCategory::Bullet => &mut [],
+    }
+}
+
+const fn weapon_mut(loadout: &mut Loadout, slot: usize) -> &mut GenericItemLockable {
+    if slot == 0 {
+        &mut loadout.weapon_one
+    } else {
+        &mut loadout.weapon_two
+    }
+}
+
+// A slot the player locked with the item already counts, as long as it isn't counted for another
+// requirement.
+fn find_locked(
+    loadout: &Loadout,
+    key: &UnlockKey,
+    category: TransactionCategory,
+    used: &[(TransactionCategory, usize)],
+) -> Option<usize> {
+    let slots = match category {
+        TransactionCategory::Weapon => vec![&loadout.weapon_one, &loadout.weapon_two],
+        TransactionCategory::Tool => loadout.tools.iter().collect(),
+        TransactionCategory::Consumable => loadout.consumables.iter().collect(),
+        TransactionCategory::Bullet => vec![],
+    };
+
+    slots.into_iter().enumerate().position(|(pos, lockable)| {
+        lockable.locked
+            && !used.contains(&(category, pos))
+            && lockable
+                .item
+                .as_ref()
+                .is_some_and(|item| UnlockKey::from_item(item) == *key)
+    })
+}
+
+// Weapons are only charged for when the weapon slots are generated, so this only checks it could
+// be bought alongside a weapon locked in the other slot.
+fn place_weapon(
+    loadout: &mut Loadout,
+    budget: &Budget,
+    config: &Config,
+    weapon: &GenericItem,
+    placed: &mut RequiredSlots,
+) -> Result<usize, &'static str> {
+    let quartermaster = config.option_exists(ToggleOption::Quartermaster);
+    let mut error = ERR_NO_SLOT;
+
+    for slot in 0..2 {
+        let other = weapon_mut(loadout, 1 - slot).clone();
+
+        if weapon_mut(loadout, slot).locked {
+            continue;
+        }
+
+        let fits = match (&other.item, other.locked) {
+            (Some(other), true) => loadout::get_valid_slots(quartermaster, &other.get_slot())
+                .contains(&weapon.get_slot()),
+            _ => true,
+        };
+
+        if !fits {
+            continue;
+        }
+
+        let mut check_budget = budget.clone();
+        let other_cost = other
+            .item
+            .as_ref()
+            .filter(|_| other.locked)
+            .map_or(0, GenericItem::get_cost);
+        let tx_res = budget::process_transaction(
+            &mut check_budget,
+            Transaction::purchase(
+                TransactionCategory::Weapon,
+                0,
+                weapon.get_cost().saturating_add(other_cost),
+                weapon.to_full_name(),
+            ),
+        );
+
+        if tx_res.is_err() {
+            error = ERR_NO_FUNDS;
+            continue;
+        }
+
+        *weapon_mut(loadout, slot) = GenericItemLockable {
+            item: Some(weapon.clone()),
+            locked: true,
+        };
+        placed.push((TransactionCategory::Weapon, slot));
+
+        return Ok(slot);
+    }
+
+    Err(error)
+}
+
+// Tools and consumables are charged for straight away, their locked slots were already paid for.
+fn place_item(
+    loadout: &mut Loadout,
+    budget: &mut Budget,
+    category: TransactionCategory,
+    item: &GenericItem,
+    placed: &mut RequiredSlots,
+) -> Result<usize, &'static str> {
+    if category == TransactionCategory::Tool
+        && loadout
+            .tools
+            .iter()
+            .any(|tool| tool.item.as_ref() == Some(item))
+    {
+        return Err(ERR_DUPLICATE_TOOL);
+    }
+
+    let Some(slot) = slots_mut(loadout, category)
+        .iter()
+        .position(|lockable| !lockable.locked)
+    else {
+        return Err(ERR_NO_SLOT);
+    };
+
+    budget::process_transaction(
+        budget,
+        Transaction::purchase(
+            category,
+            slot.try_into().unwrap_or_default(),
+            item.get_cost(),
+            item.to_full_name(),
+        ),
+    )
+    .map_err(|_| ERR_NO_FUNDS)?;
+
+    slots_mut(loadout, category)[slot] = GenericItemLockable {
+        item: Some(item.clone()),
+        locked: true,
+    };
+    placed.push((category, slot));
+
+    Ok(slot)
+}
+
+fn place(
+    loadout: &mut Loadout,
+    budget: &mut Budget,
+    config: &Config,
+    key: &UnlockKey,
+    used: &mut RequiredSlots,
+    placed: &mut RequiredSlots,
+) -> Result<(), &'static str> {
+    let Some((category, item)) = get_item(key) else {
+        return Err(ERR_UNKNOWN_ITEM);
+    };
+
+    // A locked item was picked by the player, anything else has to follow the other rules.
+    let slot = if let Some(slot) = find_locked(loadout, key, category, used) {
+        slot
+    } else if !config.item_allowed(item) {
+        return Err(ERR_NOT_ALLOWED);
+    } else if category == TransactionCategory::Weapon {
+        place_weapon(loadout, budget, config, item, placed)?
+    } else {
+        place_item(loadout, budget, category, item, placed)?
+    };
+
+    used.push((category, slot));
+
+    Ok(())
+}
+
+// Puts every required item, and one item from each group, into the loadout before anything else
+// is picked. Anything that can't be placed is reported as a rule error.
+pub fn place_required(
+    loadout: &mut Loadout,
+    budget: &mut Budget,
+    config: &Config,
+    rng: &mut StdRng,
+) -> RequiredSlots {
+    let mut used = vec![];
+    let mut placed = vec![];
+
+    for key in &config.required_items {
+        if let Err(error) = place(loadout, budget, config, key, &mut used, &mut placed) {
+            loadout.errors.push(LoadoutError::Rule {
+                error: format!("Unable to add the required {key}, {error}."),
+            });
+        }
+    }
+
+    for group in config
+        .required_groups
+        .iter()
+        .filter(|group| !group.is_empty())
+    {
+        // Already met by a locked or required item.
+        let met = group.iter().any(|key| {
+            get_item(key)
+                .is_some_and(|(category, _)| find_locked(loadout, key, category, &[]).is_some())
+        });
+
+        if met {
+            continue;
+        }
+
+        let mut options = group.clone();
+        options.shuffle(rng);
+
+        let any_placed = options
+            .iter()
+            .any(|key| place(loadout, budget, config, key, &mut used, &mut placed).is_ok());
+
+        if !any_placed {
+            let names = group
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>();
+
+            loadout.errors.push(LoadoutError::Rule {
+                error: format!("Unable to add any of {}.", names.join(", ")),
+            });
+        }
+    }
+
+    placed
+}
+
+// Required items are only locked while generating, so rerolling picks them again.
+pub fn release_required(loadout: &mut Loadout, placed: &RequiredSlots) {
+    for (category, slot) in placed {
+        let lockable = match category {
+            TransactionCategory::Weapon => Some(weapon_mut(loadout, *slot)),
+            _ => slots_mut(loadout, *category).get_mut(*slot),
+        };
+
+        if let Some(lockable) = lockable {
+            lockable.locked = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::randomizer::test_util::random_loadout;
+
+    fn has_item(lockables: &[GenericItemLockable], key: &UnlockKey) -> bool {
+        lockables.iter().any(|lockable| {
+            lockable
+                .item
+                .as_ref()
+                .is_some_and(|item| UnlockKey::from_item(item) == *key)
+        })
+    }
+
+    #[test]
+    fn required_items_are_placed() {
+        let first_aid = UnlockKey::Tool("First Aid Kit".to_string());
+        let vitality = UnlockKey::Consumable("Vitality Shot".to_string());
+
+        let (loadout, _) = random_loadout(&Config {
+            seed: Some(0),
+            required_items: vec![first_aid.clone(), vitality.clone()],
+            ..Config::default()
+        });
+
+        assert!(has_item(&loadout.tools, &first_aid));
+        assert!(has_item(&loadout.consumables, &vitality));
+        assert!(loadout.errors.is_empty(), "{:?}", loadout.errors);
+
+        // Only locked while generating.
+        assert!(loadout.tools.iter().all(|tool| !tool.locked));
+        assert!(loadout
+            .consumables
+            .iter()
+            .all(|consumable| !consumable.locked));
+    }
+
+    #[test]
+    fn one_item_of_a_group_is_placed() {
+        let group = vec![
+            UnlockKey::Tool("Knuckle Knife".to_string()),
+            UnlockKey::Tool("Choke Bombs".to_string()),
+        ];
+
+        let (loadout, _) = random_loadout(&Config {
+            seed: Some(0),
+            required_groups: vec![group.clone()],
+            ..Config::default()
+        });
+
+        assert!(group.iter().any(|key| has_item(&loadout.tools, key)));
+        assert!(loadout.errors.is_empty(), "{:?}", loadout.errors);
+    }
+
+    #[test]
+    fn unplaceable_items_are_reported() {
+        let vitality = UnlockKey::Consumable("Vitality Shot".to_string());
+        let first_aid = UnlockKey::Tool("First Aid Kit".to_string());
+
+        // Five consumables don't fit in four slots and a tool can't be taken twice.
+        let mut required_items = vec![vitality; 5];
+        required_items.extend([first_aid.clone(), first_aid]);
+
+        let (loadout, _) = random_loadout(&Config {
+            seed: Some(0),
+            required_items,
+            ..Config::default()
+        });
+
+        let rule_errors = loadout
+            .errors
+            .iter()
+            .filter(|error| matches!(error, LoadoutError::Rule { .. }))
+            .count();
+
+        assert_eq!(rule_errors, 2);
+    }
+
+    #[test]
+    fn excluded_items_are_reported() {
+        let first_aid = UnlockKey::Tool("First Aid Kit".to_string());
+
+        let config = Config {
+            required_items: vec![first_aid.clone()],
+            excluded_items: vec![first_aid.clone()],
+            ..Config::default()
+        };
+        let mut loadout = Loadout::default();
+        place_required(
+            &mut loadout,
+            &mut Budget::default(),
+            &config,
+            &mut StdRng::seed_from_u64(0),
+        );
+
+        assert!(!has_item(&loadout.tools, &first_aid));
+        assert!(matches!(
+            loadout.errors.as_slice(),
+            [LoadoutError::Rule { .. }]
+        ));
+    }
+
+    #[test]
+    fn groups_met_by_locked_items() {
+        let knife = UnlockKey::Tool("Knuckle Knife".to_string());
+
+        let mut loadout = Loadout::default();
+        loadout.tools[0] = GenericItemLockable {
+            item: CORE_SEARCH_UTIL.get_tool("Knuckle Knife").cloned(),
+            locked: true,
+        };
+
+        let config = Config {
+            required_groups: vec![vec![
+                knife.clone(),
+                UnlockKey::Tool("Choke Bombs".to_string()),
+            ]],
+            ..Config::default()
+        };
+        let placed = place_required(
+            &mut loadout,
+            &mut Budget::default(),
+            &config,
+            &mut StdRng::seed_from_u64(0),
+        );
+
+        assert!(placed.is_empty());
+        assert!(has_item(&loadout.tools, &knife));
+    }
+}
@@ -0,0 +1,281 @@
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::content::{BulletSize, ToolSlotPreference, CORE_SEARCH_UTIL};
+use crate::randomizer::{
+    balance::{self, Work},
+    loadout::{self, LoadoutError},
+    Budget, Config, Loadout,
+};
+
+// How many times a hunter is regenerated trying to meet the squad options.
+const MAX_SQUAD_ATTEMPTS: usize = 10;
+
+pub const MIN_SQUAD_SIZE: usize = 2;
+pub const MAX_SQUAD_SIZE: usize = 3;
+
+const ERR_DUPLICATE_PRIMARY: &str = "Unable to find a primary weapon no one else on the team has.";
+const ERR_NO_SUPPORT: &str = "Unable to give the team a medkit or a long range gun.";
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SquadConfig {
+    pub size: usize,
+    // Primary weapons are compared by name, so two variants of the same gun count as duplicates.
+    pub unique_primaries: bool,
+    // When shared `Config::max_cost` is for the whole team, otherwise it's for every hunter.
+    pub shared_budget: bool,
+    // At least one First Aid Kit or gun using long ammo somewhere on the team.
+    pub require_support: bool,
+    // When set hunters are rerolled with `balance::rebalance` until every hunters cost is within
+    // this much of each other.
+    pub balance_tolerance: Option<u16>,
+}
+
+impl Default for SquadConfig {
+    fn default() -> Self {
+        Self {
+            size: MAX_SQUAD_SIZE,
+            unique_primaries: true,
+            shared_budget: false,
+            require_support: false,
+            balance_tolerance: None,
+        }
+    }
+}
+
+// Every hunter keeps their own locks, `budgets` lines up with `hunters`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Squad {
+    pub seed: Option<u64>,
+    pub hunters: Vec<Loadout>,
+    pub budgets: Vec<Budget>,
+}
+
+impl Squad {
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        let size = size.clamp(MIN_SQUAD_SIZE, MAX_SQUAD_SIZE);
+
+        Self {
+            seed: None,
+            hunters: vec![Loadout::default(); size],
+            budgets: vec![Budget::default(); size],
+        }
+    }
+
+    // Hunters past the new size are dropped, new ones start empty.
+    pub fn resize(&mut self, size: usize) {
+        let size = size.clamp(MIN_SQUAD_SIZE, MAX_SQUAD_SIZE);
+
+        self.hunters.resize(size, Loadout::default());
+        self.budgets.resize(size, Budget::default());
+    }
+
+    #[must_use]
+    pub fn total_cost(&self) -> u16 {
+        self.budgets
+            .iter()
+            .fold(0, |total, budget| total.saturating_add(budget.total_cost))
+    }
+
+    #[must_use]
+    pub fn cost_spread(&self) -> u16 {
+        balance::cost_spread(&self.budgets)
+    }
+}
+
+fn primary_name(loadout: &Loadout) -> Option<&str> {
+    loadout
+        .weapon_one
+        .item
+        .as_ref()
+        .map(|weapon| weapon.name.as_str())
+}
+
+#[must_use]
+pub fn has_support(loadout: &Loadout) -> bool {
+    let medkits = CORE_SEARCH_UTIL.get_tools_by_preference(ToolSlotPreference::Medkit);
+
+    let has_medkit = loadout
+        .tools
+        .iter()
+        .filter_map(|tool| tool.item.as_ref())
+        .any(|tool| medkits.contains(&tool));
+
+    let has_long_gun = [&loadout.weapon_one, &loadout.weapon_two]
+        .into_iter()
+        .filter_map(|weapon| weapon.item.as_ref())
+        .any(|weapon| weapon.get_bullet_size() == Some(BulletSize::Long));
+
+    has_medkit || has_long_gun
+}
+
+// The config one hunter is generated with, a shared budget is spread over the hunters left so
+// anything a cheaper hunter didn't spend goes to the next one.
+fn hunter_config(
+    config: &Config,
+    squad_config: &SquadConfig,
+    spent: u16,
+    hunters_left: usize,
+    seed: u64,
+) -> Config {
+    let mut config = Config {
+        seed: Some(seed),
+        ..config.clone()
+    };
+
+    if squad_config.shared_budget {
+        let hunters_left = u16::try_from(hunters_left).unwrap_or(1).max(1);
+
+        config.max_cost = config
+            .max_cost
+            .map(|max_cost| max_cost.saturating_sub(spent) / hunters_left);
+        config.min_cost = config
+            .min_cost
+            .map(|min_cost| min_cost.saturating_sub(spent) / hunters_left);
+    }
+
+    config
+}
+
+// Generates one hunter, regenerating while they break the squad options. `others` are the hunters
+// already generated, `needs_support` is whether this hunter has to cover the support option.
+// The first attempt always runs, retries stop once there's no work left.
+#[allow(clippy::too_many_arguments)]
+fn random_hunter(
+    initial_hunter: &Loadout,
+    others: &[&Loadout],
+    config: &Config,
+    squad_config: &SquadConfig,
+    spent: u16,
+    hunters_left: usize,
+    needs_support: bool,
+    rng: &mut StdRng,
+    work: &mut Work,
+) -> (Loadout, Budget) {
+    let mut hunter = initial_hunter.clone();
+    let mut budget = Budget::default();
+    let mut duplicate_primary = false;
+    let mut missing_support = false;
+
+    for attempt in 0..MAX_SQUAD_ATTEMPTS {
+        let config = hunter_config(config, squad_config, spent, hunters_left, rng.gen());
+
+        if !work.take(&config) && attempt > 0 {
+            break;
+        }
+
+        hunter = initial_hunter.clone();
+        budget = Budget::default();
+        loadout::random(&mut hunter, &mut budget, &config);
+
+        // A locked primary was picked on purpose so it's allowed to be a duplicate.
+        duplicate_primary = squad_config.unique_primaries
+            && !hunter.weapon_one.locked
+            && primary_name(&hunter)
+                .is_some_and(|name| others.iter().any(|other| primary_name(other) == Some(name)));
+
+        missing_support = needs_support && !has_support(&hunter);
+
+        if !duplicate_primary && !missing_support {
+            break;
+        }
+    }
+
+    if duplicate_primary {
+        hunter.errors.push(LoadoutError::Rule {
+            error: ERR_DUPLICATE_PRIMARY.to_string(),
+        });
+    }
+
+    if missing_support {
+        hunter.errors.push(LoadoutError::Rule {
+            error: ERR_NO_SUPPORT.to_string(),
+        });
+    }
+
+    (hunter, budget)
+}
+
+pub fn random_squad(squad: &mut Squad, config: &Config, squad_config: &SquadConfig) {
+    // Like a single loadout the seed is kept so the whole squad can be generated again.
+    let seed = config.seed.unwrap_or_else(|| thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut work = Work::default();
+
+    let initial_hunters = squad.hunters.clone();
+    let size = initial_hunters.len();
+    let mut spent: u16 = 0;
+
+    for (pos, initial_hunter) in initial_hunters.iter().enumerate() {
+        let others = squad.hunters[..pos].iter().collect::<Vec<&Loadout>>();
+
+        // Only the last hunter is held to it, anyone before can still leave it to the others.
+        let needs_support = squad_config.require_support
+            && pos + 1 == size
+            && !others.iter().any(|other| has_support(other));
+
+        let (hunter, budget) = random_hunter(
+            initial_hunter,
+            &others,
+            config,
+            squad_config,
+            spent,
+            size - pos,
+            needs_support,
+            &mut rng,
+            &mut work,
+        );
+
+        spent = spent.saturating_add(budget.total_cost);
+        squad.hunters[pos] = hunter;
+        squad.budgets[pos] = budget;
+    }
+
+    // Rerolled hunters are checked against the whole squad, with a shared budget they get what
+    // the others didn't spend.
+    if let Some(tolerance) = squad_config.balance_tolerance {
+        balance::rebalance(
+            &mut squad.hunters,
+            &mut squad.budgets,
+            tolerance,
+            &mut rng,
+            |pos, hunters, budgets, rng| {
+                if work.is_spent() {
+                    return None;
+                }
+
+                let others = hunters
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_pos, _)| *other_pos != pos)
+                    .map(|(_, other)| other)
+                    .collect::<Vec<&Loadout>>();
+                let spent = budgets
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_pos, _)| *other_pos != pos)
+                    .fold(0, |spent: u16, (_, budget)| {
+                        spent.saturating_add(budget.total_cost)
+                    });
+                let needs_support =
+                    squad_config.require_support && !others.iter().any(|other| has_support(other));
+
+                Some(random_hunter(
+                    &initial_hunters[pos],
+                    &others,
+                    config,
+                    squad_config,
+                    spent,
+                    1,
+                    needs_support,
+                    rng,
+                    &mut work,
+                ))
+            },
+        );
+    }
+
+    squad.seed = Some(seed);
+}
@@ -0,0 +1,53 @@
+//! Loadouts shared by the randomizer tests.
+
+use crate::content::{generic_item::GenericItemLockable, CORE_SEARCH_UTIL};
+use crate::randomizer::{loadout, Budget, Config, Loadout};
+
+// Generates a loadout the same way the site does.
+pub fn random_loadout(config: &Config) -> (Loadout, Budget) {
+    let mut loadout = Loadout::default();
+    let mut budget = Budget::default();
+    loadout::random(&mut loadout, &mut budget, config);
+
+    (loadout, budget)
+}
+
+// A loadout with a locked weapon carrying custom ammo in its second ammo slot and a dual wielded
+// weapon, so every part of a weapon slot has something in it.
+pub fn armed_loadout() -> Loadout {
+    let (mut loadout, _) = random_loadout(&Config {
+        seed: Some(0),
+        ..Config::default()
+    });
+
+    let mut weapon_one = CORE_SEARCH_UTIL
+        .weapons
+        .iter()
+        .find(|weapon| {
+            weapon.additional_ammo_slots.unwrap_or(false)
+                && !weapon.get_bullet_variants().is_empty()
+        })
+        .cloned()
+        .unwrap();
+    let bullet_size = weapon_one.get_bullet_size().unwrap();
+    weapon_one.ammo_equipped = vec![
+        (bullet_size, None, 0),
+        weapon_one.get_bullet_variants()[0].clone(),
+    ];
+
+    let mut weapon_two = CORE_SEARCH_UTIL.get_dual_wield_weapons()[0].clone();
+    let bullet_size = weapon_two.get_bullet_size().unwrap();
+    weapon_two.dual_wield = true;
+    weapon_two.ammo_equipped = vec![(bullet_size, None, 0)];
+
+    loadout.weapon_one = GenericItemLockable {
+        item: Some(weapon_one),
+        locked: true,
+    };
+    loadout.weapon_two = GenericItemLockable {
+        item: Some(weapon_two),
+        locked: false,
+    };
+
+    loadout
+}
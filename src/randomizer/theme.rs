@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::content::{generic_item::CustomAmmo, Bullet, GenericItem, UsageType, UtilityType};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Theme {
+    #[default]
+    NoTheme,
+    Silent,
+    Fire,
+    Poison,
+    Explosives,
+}
+
+// What a slot gets when none of its candidates fit the theme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ThemeFallback {
+    // Picked from every candidate like there was no theme.
+    #[default]
+    AnyItem,
+    // Weapons and items are left empty, weapons keep their default ammo.
+    Empty,
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::NoTheme => "NoTheme",
+                Self::Silent => "Silent",
+                Self::Fire => "Fire",
+                Self::Poison => "Poison",
+                Self::Explosives => "Explosives",
+            }
+        )
+    }
+}
+
+impl TryFrom<String> for Theme {
+    type Error = &'static str;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match &*val {
+            "NoTheme" => Ok(Self::NoTheme),
+            "Silent" => Ok(Self::Silent),
+            "Fire" => Ok(Self::Fire),
+            "Poison" => Ok(Self::Poison),
+            "Explosives" => Ok(Self::Explosives),
+            _ => Err("Invalid theme"),
+        }
+    }
+}
+
+impl fmt::Display for ThemeFallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::AnyItem => "AnyItem",
+                Self::Empty => "Empty",
+            }
+        )
+    }
+}
+
+impl TryFrom<String> for ThemeFallback {
+    type Error = &'static str;
+
+    fn try_from(val: String) -> Result<Self, Self::Error> {
+        match &*val {
+            "AnyItem" => Ok(Self::AnyItem),
+            "Empty" => Ok(Self::Empty),
+            _ => Err("Invalid theme fallback"),
+        }
+    }
+}
+
+impl Theme {
+    #[must_use]
+    pub const fn utility_type(self) -> Option<UtilityType> {
+        match self {
+            Self::NoTheme => None,
+            Self::Silent => Some(UtilityType::Silent),
+            Self::Fire => Some(UtilityType::Fire),
+            Self::Poison => Some(UtilityType::Poison),
+            Self::Explosives => Some(UtilityType::Explosion),
+        }
+    }
+
+    fn bullet_matches(self, bullet: &Bullet) -> bool {
+        self.utility_type().is_some_and(|utility_type| {
+            bullet
+                .types
+                .as_ref()
+                .is_some_and(|types| types.contains(&utility_type))
+        })
+    }
+
+    // Guns are matched through their bullets, ammo variants only count when they can be equipped.
+    #[must_use]
+    pub fn item_matches(self, item: &GenericItem, custom_ammo: bool) -> bool {
+        let Some(utility_type) = self.utility_type() else {
+            return true;
+        };
+
+        item.usage_types.iter().any(|usage_type| match usage_type {
+            UsageType::Shoot { bullet_types, .. }
+            | UsageType::ShootSecondary { bullet_types, .. } => bullet_types
+                .iter()
+                .filter(|bullet| custom_ammo || bullet.name.is_none())
+                .any(|bullet| self.bullet_matches(bullet)),
+            UsageType::BasicMelee { types, .. }
+            | UsageType::HeavyMelee { types, .. }
+            | UsageType::Placeable { types, .. }
+            | UsageType::Throw { types, .. }
+            | UsageType::ThrowLight { types, .. }
+            | UsageType::Use { types, .. } => types.contains(&utility_type),
+        })
+    }
+
+    #[must_use]
+    pub fn default_ammo_matches(self, weapon: &GenericItem) -> bool {
+        weapon
+            .get_bullets()
+            .into_iter()
+            .filter(|bullet| bullet.name.is_none())
+            .any(|bullet| self.bullet_matches(bullet))
+    }
+
+    #[must_use]
+    pub fn ammo_matches(self, weapon: &GenericItem, ammo: &CustomAmmo) -> bool {
+        ammo.1.as_ref().map_or_else(
+            || self.default_ammo_matches(weapon),
+            |variant| {
+                weapon
+                    .get_bullet(variant)
+                    .is_some_and(|bullet| self.bullet_matches(bullet))
+            },
+        )
+    }
+}
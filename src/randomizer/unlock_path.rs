@@ -0,0 +1,97 @@
+use crate::content::{
+    core_search_util::MAX_REQUIREMENT_DEPTH, BulletVariant, GenericItem, PreviousRequirement,
+    Requirement,
+};
+use crate::randomizer::unlocks::UnlockKey;
+
+// A single thing to unlock along with what has to be done for it, previous requirements are not
+// listed since they are earlier steps in the path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnlockStep {
+    pub unlock: UnlockKey,
+    pub requirements: Vec<Requirement>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnlockPath {
+    // Ordered so every step only depends on the steps before it, the target is always last.
+    pub steps: Vec<UnlockStep>,
+    // References in the requirements that don't match anything in `CORE_SEARCH_UTIL`.
+    pub dangling: Vec<PreviousRequirement>,
+}
+
+impl UnlockPath {
+    // The bloodline rank needed for every step in the path.
+    #[must_use]
+    pub fn get_rank(&self) -> u8 {
+        self.steps
+            .iter()
+            .flat_map(|step| &step.requirements)
+            .filter_map(|requirement| match requirement {
+                Requirement::Rank(rank) => Some(*rank),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+// Keys that don't match anything in `CORE_SEARCH_UTIL` are left out of the path.
+fn add_steps(path: &mut UnlockPath, unlock: UnlockKey, depth: u8) {
+    if depth > MAX_REQUIREMENT_DEPTH || path.steps.iter().any(|step| step.unlock == unlock) {
+        return;
+    }
+
+    let Some(requirements) = unlock.get_requirements() else {
+        return;
+    };
+
+    if let Some(weapon) = unlock.get_ammo_weapon() {
+        add_steps(path, weapon, depth + 1);
+    }
+
+    let mut step_requirements = vec![];
+
+    for requirement in requirements {
+        if let Requirement::PreviousRequirements(previous) = &requirement {
+            let previous_unlock = UnlockKey::from_previous_requirement(previous);
+
+            if previous_unlock.get_requirements().is_some() {
+                add_steps(path, previous_unlock, depth + 1);
+            } else if !path.dangling.contains(previous) {
+                path.dangling.push(previous.clone());
+            }
+        } else {
+            step_requirements.push(requirement);
+        }
+    }
+
+    path.steps.push(UnlockStep {
+        unlock,
+        requirements: step_requirements,
+    });
+}
+
+#[must_use]
+pub fn plan_item_unlock(item: &GenericItem) -> UnlockPath {
+    let mut path = UnlockPath::default();
+    add_steps(&mut path, UnlockKey::from_item(item), 0);
+    path
+}
+
+// Ammo requirements live on the base weapon, variants share them.
+#[must_use]
+pub fn plan_ammo_unlock(weapon: &GenericItem, ammo: &BulletVariant) -> UnlockPath {
+    let mut path = UnlockPath::default();
+
+    add_steps(
+        &mut path,
+        UnlockKey::Ammo {
+            weapon: weapon.name.clone(),
+            ammo: ammo.clone(),
+        },
+        0,
+    );
+
+    path
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::content::{
+    core_search_util::MAX_REQUIREMENT_DEPTH, BulletVariant, GenericItem, PreviousRequirement,
+    Requirement, WeaponVariant, CORE_SEARCH_UTIL,
+};
+
+// Identifies anything that can be unlocked, these mirror `PreviousRequirement` so the unlock graph
+// in the json files can be followed without converting names back and forth.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "t", content = "c", rename_all = "snake_case")]
+pub enum UnlockKey {
+    Weapon {
+        name: String,
+        variant: Option<WeaponVariant>,
+    },
+    Tool(String),
+    Consumable(String),
+    // Ammo is unlocked for the base weapon and shared with every variant of it.
+    Ammo {
+        weapon: String,
+        ammo: BulletVariant,
+    },
+}
+
+impl UnlockKey {
+    #[must_use]
+    pub fn from_item(item: &GenericItem) -> Self {
+        if CORE_SEARCH_UTIL.name_is_tool(&item.name) {
+            Self::Tool(item.name.clone())
+        } else if CORE_SEARCH_UTIL.name_is_consumable(&item.name) {
+            Self::Consumable(item.name.clone())
+        } else {
+            Self::Weapon {
+                name: item.name.clone(),
+                variant: item.variant.clone(),
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn from_previous_requirement(previous: &PreviousRequirement) -> Self {
+        match previous {
+            PreviousRequirement::Weapon { weapon, variant } => Self::Weapon {
+                name: weapon.clone(),
+                variant: variant.clone(),
+            },
+            PreviousRequirement::Tool { tool } => Self::Tool(tool.clone()),
+            PreviousRequirement::Consumable { consumable } => Self::Consumable(consumable.clone()),
+            PreviousRequirement::Ammo { weapon, ammo } => Self::Ammo {
+                weapon: weapon.clone(),
+                ammo: ammo.clone(),
+            },
+        }
+    }
+
+    // Ammo can't be bought without the weapon it's for, so the base weapon is unlocked before it.
+    #[must_use]
+    pub fn get_ammo_weapon(&self) -> Option<Self> {
+        match self {
+            Self::Ammo { weapon, .. } => Some(Self::Weapon {
+                name: weapon.clone(),
+                variant: None,
+            }),
+            _ => None,
+        }
+    }
+
+    // `None` if the key doesn't match anything in `CORE_SEARCH_UTIL`.
+    #[must_use]
+    pub fn get_requirements(&self) -> Option<Vec<Requirement>> {
+        match self {
+            Self::Weapon { name, variant } => CORE_SEARCH_UTIL
+                .get_weapon(name, variant.as_ref())
+                .map(|weapon| weapon.requirements.clone()),
+            Self::Tool(name) => CORE_SEARCH_UTIL
+                .get_tool(name)
+                .map(|tool| tool.requirements.clone()),
+            Self::Consumable(name) => CORE_SEARCH_UTIL
+                .get_consumable(name)
+                .map(|consumable| consumable.requirements.clone()),
+            Self::Ammo { weapon, ammo } => CORE_SEARCH_UTIL
+                .get_weapon(weapon, None)
+                .and_then(|weapon| weapon.get_bullet(ammo))
+                .map(|bullet| bullet.requirements.clone().unwrap_or_default()),
+        }
+    }
+}
+
+impl fmt::Display for UnlockKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Weapon {
+                name,
+                variant: Some(variant),
+            } => write!(f, "{name} {variant}"),
+            Self::Weapon {
+                name,
+                variant: None,
+            }
+            | Self::Tool(name)
+            | Self::Consumable(name) => write!(f, "{name}"),
+            Self::Ammo { weapon, ammo } => write!(f, "{ammo} ammo for the {weapon}"),
+        }
+    }
+}
+
+// What a player has unlocked. Anything listed in `items` is unlocked, anything else is unlocked
+// once every one of its requirements is met, with rank requirements checked against the bloodline
+// rank passed in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Unlocks {
+    pub items: Vec<UnlockKey>,
+    pub requirements: Vec<Requirement>,
+}
+
+impl Unlocks {
+    // Unlocking something also unlocks everything it depends on.
+    pub fn unlock(&mut self, key: UnlockKey) {
+        self.unlock_with_depth(key, 0);
+    }
+
+    fn unlock_with_depth(&mut self, key: UnlockKey, depth: u8) {
+        if depth > MAX_REQUIREMENT_DEPTH || self.items.contains(&key) {
+            return;
+        }
+
+        let requirements = key.get_requirements().unwrap_or_default();
+
+        if let Some(weapon) = key.get_ammo_weapon() {
+            self.unlock_with_depth(weapon, depth + 1);
+        }
+
+        self.items.push(key);
+
+        for requirement in requirements {
+            match requirement {
+                Requirement::PreviousRequirements(previous) => self
+                    .unlock_with_depth(UnlockKey::from_previous_requirement(&previous), depth + 1),
+                // Rank isn't something that can be marked, it comes from the bloodline rank.
+                Requirement::Rank(_) => {}
+                requirement => self.meet_requirement(requirement),
+            }
+        }
+    }
+
+    pub fn lock(&mut self, key: &UnlockKey) {
+        self.items.retain(|item| item != key);
+    }
+
+    pub fn meet_requirement(&mut self, requirement: Requirement) {
+        if !self.requirements.contains(&requirement) {
+            self.requirements.push(requirement);
+        }
+    }
+
+    pub fn unmeet_requirement(&mut self, requirement: &Requirement) {
+        self.requirements.retain(|met| met != requirement);
+    }
+
+    #[must_use]
+    pub fn is_marked(&self, key: &UnlockKey) -> bool {
+        self.items.contains(key)
+    }
+
+    #[must_use]
+    pub fn is_unlocked(&self, key: &UnlockKey, max_rank: u8) -> bool {
+        self.is_unlocked_with_depth(key, max_rank, 0)
+    }
+
+    fn is_unlocked_with_depth(&self, key: &UnlockKey, max_rank: u8, depth: u8) -> bool {
+        if self.items.contains(key) {
+            return true;
+        }
+
+        if depth > MAX_REQUIREMENT_DEPTH {
+            return false;
+        }
+
+        key.get_requirements().is_some_and(|requirements| {
+            requirements.iter().all(|requirement| match requirement {
+                Requirement::Rank(rank) => *rank <= max_rank,
+                Requirement::PreviousRequirements(previous) => self.is_unlocked_with_depth(
+                    &UnlockKey::from_previous_requirement(previous),
+                    max_rank,
+                    depth + 1,
+                ),
+                requirement => self.requirements.contains(requirement),
+            })
+        })
+    }
+
+    #[must_use]
+    pub fn item_unlocked(&self, item: &GenericItem, max_rank: u8) -> bool {
+        self.is_unlocked(&UnlockKey::from_item(item), max_rank)
+    }
+
+    #[must_use]
+    pub fn ammo_unlocked(&self, weapon: &GenericItem, ammo: &BulletVariant, max_rank: u8) -> bool {
+        self.item_unlocked(weapon, max_rank)
+            && self.is_unlocked(
+                &UnlockKey::Ammo {
+                    weapon: weapon.name.clone(),
+                    ammo: ammo.clone(),
+                },
+                max_rank,
+            )
+    }
+}
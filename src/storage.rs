@@ -0,0 +1,67 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+// Bump this when a stored type changes in a way that old data can no longer be read, anything saved
+// under an older version is ignored instead of breaking the page.
+pub const STORAGE_VERSION: u32 = 1;
+
+pub const UNLOCKS_KEY: &str = "hunt-app-unlocks";
+pub const LOADOUT_KEY: &str = "hunt-app-loadout";
+pub const BUDGET_KEY: &str = "hunt-app-budget";
+pub const CONFIG_KEY: &str = "hunt-app-config";
+pub const PRESETS_KEY: &str = "hunt-app-presets";
+pub const SQUAD_KEY: &str = "hunt-app-squad";
+pub const SQUAD_CONFIG_KEY: &str = "hunt-app-squad-config";
+
+#[derive(Serialize)]
+struct VersionedRef<'a, T> {
+    version: u32,
+    data: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Versioned<T> {
+    version: u32,
+    data: T,
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+pub fn save<T: Serialize>(key: &str, data: &T) {
+    let Some(storage) = local_storage() else {
+        log::error!("Local storage is not available, unable to save {key}");
+        return;
+    };
+
+    match serde_json::to_string(&VersionedRef {
+        version: STORAGE_VERSION,
+        data,
+    }) {
+        Ok(json) => {
+            if storage.set_item(key, &json).is_err() {
+                log::error!("Unable to save {key} to local storage");
+            }
+        }
+        Err(err) => log::error!("Unable to serialize {key}: {err}"),
+    }
+}
+
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let json = local_storage()?.get_item(key).ok()??;
+
+    match serde_json::from_str::<Versioned<T>>(&json) {
+        Ok(stored) if stored.version == STORAGE_VERSION => Some(stored.data),
+        Ok(stored) => {
+            log::warn!(
+                "Ignoring {key} saved with storage version {}",
+                stored.version
+            );
+            None
+        }
+        Err(err) => {
+            log::error!("Unable to read {key} from local storage: {err}");
+            None
+        }
+    }
+}